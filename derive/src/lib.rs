@@ -0,0 +1,133 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, Ident, Member};
+
+/// Turns a struct into chart data, with its fields marked as the parts of a point:
+/// ```rust,ignore
+///   #[derive(ChartData)]
+///   struct Measurement {
+///       #[chart(x)]
+///       time: String,
+///       #[chart(y)]
+///       value: f64,
+///       #[chart(label)]
+///       station: String,
+///   }
+///
+///   let data = measurements.to_dataset_data();
+/// ```
+/// `x` and `y` are required, and `label` (shown as the point's description) and `r`
+/// (the radius of a bubble) are optional. The fields must implement `Display`, except for
+/// an `x` field that is a date implementing `ChartDate`, which becomes a timestamp.
+///
+/// Generates `From<&T>` and `From<T>` for `XYPoint`, and `ChartPoint`, so that `Vec<T>` and `&[T]`
+/// implement `ChartPointsExt`.
+#[proc_macro_derive(ChartData, attributes(chart))]
+pub fn derive_chart_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    chart_data(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Roles {
+    x: Option<Member>,
+    y: Option<Member>,
+    label: Option<Member>,
+    r: Option<Member>,
+}
+
+fn chart_data(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(s) => &s.fields,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "ChartData can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut roles = Roles::default();
+    for (i, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        };
+
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("chart")) {
+            let role: Ident = attr.parse_args()?;
+            let slot = match role.to_string().as_str() {
+                "x" => &mut roles.x,
+                "y" => &mut roles.y,
+                "label" => &mut roles.label,
+                "r" => &mut roles.r,
+                _ => {
+                    return Err(Error::new(
+                        role.span(),
+                        "expected one of `x`, `y`, `label` or `r`",
+                    ))
+                }
+            };
+
+            if slot.is_some() {
+                return Err(Error::new(
+                    role.span(),
+                    format!("more than one `{role}` field"),
+                ));
+            }
+            *slot = Some(member.clone());
+        }
+    }
+
+    let missing = |role: &str| {
+        Error::new(
+            match fields {
+                Fields::Unit => input.ident.span(),
+                _ => fields.span(),
+            },
+            format!("no field is marked `#[chart({role})]`"),
+        )
+    };
+    let x = roles.x.ok_or_else(|| missing("x"))?;
+    let y = roles.y.ok_or_else(|| missing("y"))?;
+    let optional = [("description", roles.label), ("r", roles.r)]
+        .into_iter()
+        .filter_map(|(name, member)| {
+            let name = Ident::new(name, proc_macro2::Span::call_site());
+            member.map(
+                |member| quote!(#name: ::std::string::ToString::to_string(&value.#member).into(),),
+            )
+        });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::From<&#ident #ty_generics> for ::chart_js_rs::XYPoint #where_clause {
+            fn from(value: &#ident #ty_generics) -> Self {
+                use ::chart_js_rs::dates::derive_support::{DateValue as _, DisplayValue as _};
+                ::chart_js_rs::XYPoint {
+                    x: (&value.#x).chart_value(),
+                    y: ::std::string::ToString::to_string(&value.#y).into(),
+                    #(#optional)*
+                    ..::core::default::Default::default()
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#ident #ty_generics> for ::chart_js_rs::XYPoint #where_clause {
+            fn from(value: #ident #ty_generics) -> Self {
+                (&value).into()
+            }
+        }
+
+        impl #impl_generics ::chart_js_rs::ChartPoint for #ident #ty_generics #where_clause {
+            fn to_xy_point(&self) -> ::chart_js_rs::XYPoint {
+                self.into()
+            }
+        }
+    })
+}
@@ -165,7 +165,7 @@ impl Model {
                         spanGaps: true.into(),
                         segment: Segment {
                             borderDash: FnWithArgs::new().arg("ctx").body("ctx.p0.skip || ctx.p1.skip ? [2, 2] : undefined"),
-                            borderColor: FnWithArgs::new().arg("ctx").body("ctx.p0.skip || ctx.p1.skip ? 'rgb(0, 0, 0, 0.2)' : (ctx.p0.parsed.y > ctx.p1.parsed.y) ? 'rgb(255,0,0,1)' : 'rgb(0,255,0,1)'"),
+                            borderColor: FnWithArgs::new().arg("ctx").body("ctx.p0.skip || ctx.p1.skip ? 'rgba(0, 0, 0, 0.2)' : (ctx.p0.parsed.y > ctx.p1.parsed.y) ? 'rgba(255,0,0,1)' : 'rgba(0,255,0,1)'"),
                         }
                         .into(),
                         pointRadius: 4.into(),
@@ -199,10 +199,7 @@ impl Model {
             options: ChartOptions {
                 scales: Some(HashMap::from([(
                     "x".into(),
-                    ChartScale {
-                        r#type: "linear".into(),
-                        ..Default::default()
-                    },
+                    ChartScale::Linear(Default::default()),
                 )])),
                 maintainAspectRatio: Some(false),
                 ..Default::default() // always use `..Default::default()` to make sure this works in the future
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{
+    types::*,
+    validate::{validate, Problem},
+    ChartError,
+};
+
+/// An x axis of a chart, returned by [`ChartOptions::x_axis`], to draw datasets on
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct XAxis(String);
+
+/// A y axis of a chart, returned by [`ChartOptions::y_axis`], to draw datasets on
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct YAxis(String);
+
+impl XAxis {
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+}
+
+impl YAxis {
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&XAxis> for String {
+    fn from(axis: &XAxis) -> Self {
+        axis.0.clone()
+    }
+}
+
+impl From<&YAxis> for String {
+    fn from(axis: &YAxis) -> Self {
+        axis.0.clone()
+    }
+}
+
+/// Declares the axes of a chart, so datasets refer to them by handle rather than by id:
+/// ```rust,ignore
+///   let (revenue, orders) = options.dual_y_axes(LinearScale::default(), LinearScale::default());
+///
+///   datasets: vec![
+///       XYDataset { label: "Revenue".into(), ..Default::default() }.on_y_axis(&revenue),
+///       XYDataset { label: "Orders".into(), ..Default::default() }.on_y_axis(&orders),
+///   ],
+/// ```
+impl<A: Annotation> ChartOptions<A> {
+    /// Adds `scale` as an x axis with this id, replacing any scale with the same id
+    pub fn x_axis(&mut self, id: &str, scale: impl Into<ChartScale>) -> XAxis {
+        self.add_axis(id, scale.into(), IndexAxis::X);
+        XAxis(id.to_string())
+    }
+
+    /// Adds `scale` as a y axis with this id, replacing any scale with the same id
+    pub fn y_axis(&mut self, id: &str, scale: impl Into<ChartScale>) -> YAxis {
+        self.add_axis(id, scale.into(), IndexAxis::Y);
+        YAxis(id.to_string())
+    }
+
+    /// Adds a y axis on each side of the chart, `y` on the left and `y1` on the right.
+    /// Only the left one draws grid lines, so the two grids don't overlap
+    pub fn dual_y_axes(
+        &mut self,
+        left: impl Into<ChartScale>,
+        right: impl Into<ChartScale>,
+    ) -> (YAxis, YAxis) {
+        let mut left = left.into();
+        left.common_mut().position = ScalePosition::Left;
+
+        let mut right = right.into();
+        let common = right.common_mut();
+        common.position = ScalePosition::Right;
+        common
+            .grid
+            .get_or_insert_with(Default::default)
+            .drawOnChartArea = Some(false);
+
+        (self.y_axis("y", left), self.y_axis("y1", right))
+    }
+
+    fn add_axis(&mut self, id: &str, mut scale: ChartScale, axis: IndexAxis) {
+        scale.common_mut().axis = axis;
+        self.scales
+            .get_or_insert_with(HashMap::new)
+            .insert(id.to_string(), scale);
+    }
+}
+
+impl XYDataset {
+    /// Draws the dataset on `axis`
+    pub fn on_x_axis(mut self, axis: &XAxis) -> Self {
+        self.xAxisID = axis.into();
+        self
+    }
+
+    /// Draws the dataset on `axis`
+    pub fn on_y_axis(mut self, axis: &YAxis) -> Self {
+        self.yAxisID = axis.into();
+        self
+    }
+}
+
+/// Returns an error if a dataset is drawn on a scale that isn't configured, which Chart.js
+/// would otherwise add with the default options. The default axes, `x` and `y`, are always allowed.
+/// This is the [`Problem::MissingAxis`] check of [`validate`], on its own
+pub fn check_axes(chart: &impl Serialize) -> Result<(), ChartError> {
+    let config = serde_json::to_value(chart).map_err(|e| ChartError::Serde(e.to_string()))?;
+    match validate(&config)
+        .into_iter()
+        .find(|d| matches!(d.problem, Problem::MissingAxis(_)))
+    {
+        Some(diagnostic) => Err(ChartError::InvalidChart(diagnostic.to_string())),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bar::Bar, ChartExt};
+
+    #[test]
+    fn datasets_on_configured_axes() {
+        let mut bar = Bar::<NoAnnotations>::default();
+        let (left, right) = bar
+            .options
+            .dual_y_axes(LinearScale::default(), LinearScale::default());
+        bar.data.datasets = vec![
+            XYDataset::default().on_y_axis(&left),
+            XYDataset::default().on_y_axis(&right),
+            // the default axes are always allowed
+            XYDataset {
+                xAxisID: "x".into(),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(bar.check_axes(), Ok(()));
+
+        bar.data.datasets.push(XYDataset {
+            yAxisID: "y2".into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            bar.check_axes(),
+            Err(ChartError::InvalidChart(
+                "data.datasets[3].yAxisID: the dataset is drawn on scale `y2`, which isn't configured"
+                    .into()
+            ))
+        );
+    }
+}
@@ -0,0 +1,639 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{
+    axes::check_axes,
+    bar::Bar,
+    doughnut::Doughnut,
+    pie::Pie,
+    scatter::Scatter,
+    types::*,
+    validate::{validate, Problem},
+    ChartError, Color, Paint,
+};
+
+/// Builds a chart one setting at a time, instead of with nested struct literals.
+/// Checks that the chart can be rendered in [`ChartBuilder::build`]:
+/// ```rust,ignore
+///   let chart = Doughnut::<NoAnnotations>::builder()
+///       .id("browsers")
+///       .labels(["Firefox", "Chrome", "Safari"])
+///       .title("Visitors by browser")
+///       .dataset(|d| d.data([4, 8, 15]).colors(["#ff9f40", "#36a2eb", "#9966ff"]))
+///       .build()?;
+/// ```
+pub struct ChartBuilder<C: ChartParts>(C, Option<ChartError>);
+
+/// The parts of a chart that [`ChartBuilder`] fills in
+pub trait ChartParts: Default {
+    type Annotation: Annotation + Default;
+    type Builder: DatasetBuilder;
+
+    fn id(&mut self) -> &mut String;
+    fn labels(&mut self) -> &mut Option<Vec<NumberOrDateString>>;
+    fn datasets(&mut self) -> &mut Vec<<Self::Builder as DatasetBuilder>::Dataset>;
+    fn options(&mut self) -> &mut ChartOptions<Self::Annotation>;
+}
+
+pub trait DatasetBuilder: Default {
+    type Dataset;
+
+    fn build(self) -> Self::Dataset;
+
+    /// The number of values in the dataset, if they are matched to the chart's labels
+    fn len(dataset: &Self::Dataset) -> Option<usize>;
+
+    fn colors(dataset: &Self::Dataset) -> Vec<&Paint>;
+}
+
+macro_rules! impl_chart_parts {
+    ($chart:ident, $builder:ident) => {
+        impl<A: Annotation + Default> ChartParts for $chart<A> {
+            type Annotation = A;
+            type Builder = $builder;
+
+            fn id(&mut self) -> &mut String {
+                &mut self.id
+            }
+            fn labels(&mut self) -> &mut Option<Vec<NumberOrDateString>> {
+                &mut self.data.labels
+            }
+            fn datasets(&mut self) -> &mut Vec<<Self::Builder as DatasetBuilder>::Dataset> {
+                &mut self.data.datasets
+            }
+            fn options(&mut self) -> &mut ChartOptions<Self::Annotation> {
+                &mut self.options
+            }
+        }
+
+        impl<A: Annotation + Default> $chart<A> {
+            pub fn builder() -> ChartBuilder<Self> {
+                ChartBuilder(Self::default(), None)
+            }
+        }
+    };
+}
+impl_chart_parts!(Bar, XYDatasetBuilder);
+impl_chart_parts!(Scatter, XYDatasetBuilder);
+impl_chart_parts!(Pie, SinglePointDatasetBuilder);
+impl_chart_parts!(Doughnut, SinglePointDatasetBuilder);
+
+impl<C: ChartParts> ChartBuilder<C> {
+    /// The id of the canvas the chart is rendered on
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        *self.0.id() = id.into();
+        self
+    }
+
+    pub fn labels<L: Into<NumberOrDateString>>(
+        mut self,
+        labels: impl IntoIterator<Item = L>,
+    ) -> Self {
+        *self.0.labels() = Some(labels.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn dataset(mut self, f: impl FnOnce(C::Builder) -> C::Builder) -> Self {
+        let dataset = f(C::Builder::default()).build();
+        self.0.datasets().push(dataset);
+        self
+    }
+
+    pub fn scale(
+        mut self,
+        id: impl Into<String>,
+        f: impl FnOnce(ScaleBuilder) -> ScaleBuilder,
+    ) -> Self {
+        let id = id.into();
+        let scales = self.0.options().scales.get_or_insert_with(HashMap::new);
+        let scale = scales.remove(&id).unwrap_or_default();
+        let ScaleBuilder(scale, unsupported) = f(ScaleBuilder(scale, Vec::new()));
+        if let Some(option) = unsupported.first().filter(|_| self.1.is_none()) {
+            self.1 = Some(ChartError::InvalidChart(format!(
+                "`{option}` only applies to linear scales, but scale `{id}` isn't one"
+            )));
+        }
+        scales.insert(id, scale);
+        self
+    }
+
+    pub fn title(mut self, text: impl Into<String>) -> Self {
+        self.plugins().title = Some(Title {
+            text: text.into(),
+            display: Some(true),
+            ..Default::default()
+        });
+        self
+    }
+
+    pub fn legend(mut self, display: bool) -> Self {
+        self.plugins()
+            .legend
+            .get_or_insert_with(Default::default)
+            .display = Some(display);
+        self
+    }
+
+    pub fn responsive(mut self, responsive: bool) -> Self {
+        self.0.options().responsive = Some(responsive);
+        self
+    }
+
+    pub fn maintain_aspect_ratio(mut self, maintain: bool) -> Self {
+        self.0.options().maintainAspectRatio = Some(maintain);
+        self
+    }
+
+    pub fn locale(mut self, locale: impl Into<Locale>) -> Self {
+        self.0.options().locale = locale.into();
+        self
+    }
+
+    /// For any options that don't have a method of their own
+    pub fn options(mut self, f: impl FnOnce(&mut ChartOptions<C::Annotation>)) -> Self {
+        f(self.0.options());
+        self
+    }
+
+    fn plugins(&mut self) -> &mut ChartPlugins<C::Annotation> {
+        self.0
+            .options()
+            .plugins
+            .get_or_insert_with(Default::default)
+    }
+
+    /// Returns the chart, or an error if it has no id, a scale has an option its type doesn't,
+    /// a dataset is drawn on a scale that isn't configured, a dataset doesn't have a value
+    /// for each label, or a color is invalid
+    pub fn build(mut self) -> Result<C, ChartError>
+    where
+        C: Serialize,
+    {
+        if self.0.id().is_empty() {
+            return Err(ChartError::InvalidChart("the chart has no id".into()));
+        }
+        if let Some(error) = self.1.take() {
+            return Err(error);
+        }
+        check_axes(&self.0)?;
+
+        let labels = self.0.labels().as_ref().map(Vec::len);
+
+        for (i, dataset) in self.0.datasets().iter().enumerate() {
+            if let (Some(labels), Some(len)) = (labels, C::Builder::len(dataset)) {
+                if labels != len {
+                    return Err(ChartError::InvalidChart(format!(
+                        "dataset {i} has {len} values for {labels} labels"
+                    )));
+                }
+            }
+
+            if let Some(color) = C::Builder::colors(dataset)
+                .into_iter()
+                .find_map(|p| match p {
+                    Paint::Color(c) if !c.is_empty() && !c.is_valid() => Some(c),
+                    _ => None,
+                })
+            {
+                return Err(ChartError::InvalidColor(color.as_str().into()));
+            }
+        }
+
+        // the colors of the options, e.g. of the title and the grid
+        let config = serde_json::to_value(&self.0).map_err(|e| ChartError::Serde(e.to_string()))?;
+        if let Some(Problem::InvalidColor(color)) = validate(&config)
+            .into_iter()
+            .map(|d| d.problem)
+            .find(|p| matches!(p, Problem::InvalidColor(_)))
+        {
+            return Err(ChartError::InvalidColor(color));
+        }
+
+        Ok(self.0)
+    }
+}
+
+#[derive(Default)]
+pub struct XYDatasetBuilder(XYDataset);
+
+impl DatasetBuilder for XYDatasetBuilder {
+    type Dataset = XYDataset;
+
+    fn build(self) -> XYDataset {
+        self.0
+    }
+
+    /// Points with their own `x` aren't matched to labels
+    fn len(dataset: &XYDataset) -> Option<usize> {
+        let data = dataset.data.0.as_array()?;
+        (!data.is_empty() && data.iter().all(|v| !v.is_object())).then_some(data.len())
+    }
+
+    fn colors(dataset: &XYDataset) -> Vec<&Paint> {
+        vec![
+            &dataset.backgroundColor,
+            &dataset.borderColor,
+            &dataset.hoverBackgroundColor,
+            &dataset.hoverBorderColor,
+            &dataset.pointBackgroundColor,
+            &dataset.pointBorderColor,
+            &dataset.pointHoverBackgroundColor,
+        ]
+    }
+}
+
+impl XYDatasetBuilder {
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.0.label = label.into();
+        self
+    }
+
+    /// Points, e.g. a `Vec<XYPoint>` or a list of [`ChartPoint`]s, [`Rows`],
+    /// or anything else converted with [`DatasetDataExt`]
+    pub fn data(mut self, data: impl Into<DatasetData>) -> Self {
+        self.0.data = data.into();
+        self
+    }
+
+    /// The keys of the x and y values, for data of [`Rows`]
+    pub fn parsing(mut self, x_key: impl Into<String>, y_key: impl Into<String>) -> Self {
+        self.0.parsing = Some(Parsing::new(x_key, y_key));
+        self
+    }
+
+    /// The line or border and the fill in the same color, the fill at half opacity
+    pub fn color(mut self, color: impl Into<Color>) -> Self {
+        let color = color.into();
+        self.0.backgroundColor = color.alpha(0.5).into();
+        self.0.borderColor = color.into();
+        self
+    }
+
+    pub fn background_color(mut self, paint: impl Into<Paint>) -> Self {
+        self.0.backgroundColor = paint.into();
+        self
+    }
+
+    pub fn border_color(mut self, paint: impl Into<Paint>) -> Self {
+        self.0.borderColor = paint.into();
+        self
+    }
+
+    pub fn border_width(mut self, width: impl Into<NumberString>) -> Self {
+        self.0.borderWidth = width.into();
+        self
+    }
+
+    /// Draws the dataset as another type of chart, e.g. a line on a bar chart
+    pub fn r#type(mut self, r#type: impl Into<String>) -> Self {
+        self.0.r#type = r#type.into();
+        self
+    }
+
+    pub fn fill(mut self, fill: impl Into<FillTarget>) -> Self {
+        self.0.fill = fill.into();
+        self
+    }
+
+    pub fn tension(mut self, tension: impl Into<NumberString>) -> Self {
+        self.0.tension = tension.into();
+        self
+    }
+
+    pub fn point_radius(mut self, radius: impl Into<NumberString>) -> Self {
+        self.0.pointRadius = radius.into();
+        self
+    }
+
+    pub fn x_axis(mut self, id: impl Into<String>) -> Self {
+        self.0.xAxisID = id.into();
+        self
+    }
+
+    pub fn y_axis(mut self, id: impl Into<String>) -> Self {
+        self.0.yAxisID = id.into();
+        self
+    }
+
+    pub fn stack(mut self, stack: impl Into<String>) -> Self {
+        self.0.stack = stack.into();
+        self
+    }
+
+    pub fn order(mut self, order: impl Into<NumberString>) -> Self {
+        self.0.order = order.into();
+        self
+    }
+
+    /// For any fields that don't have a method of their own
+    pub fn with(mut self, f: impl FnOnce(&mut XYDataset)) -> Self {
+        f(&mut self.0);
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct SinglePointDatasetBuilder(SinglePointDataset);
+
+impl DatasetBuilder for SinglePointDatasetBuilder {
+    type Dataset = SinglePointDataset;
+
+    fn build(self) -> SinglePointDataset {
+        self.0
+    }
+
+    fn len(dataset: &SinglePointDataset) -> Option<usize> {
+        Some(dataset.data.len())
+    }
+
+    fn colors(dataset: &SinglePointDataset) -> Vec<&Paint> {
+        dataset
+            .backgroundColor
+            .iter()
+            .chain([
+                &dataset.borderColor,
+                &dataset.hoverBackgroundColor,
+                &dataset.hoverBorderColor,
+                &dataset.pointBackgroundColor,
+                &dataset.pointBorderColor,
+                &dataset.pointHoverBackgroundColor,
+            ])
+            .collect()
+    }
+}
+
+impl SinglePointDatasetBuilder {
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.0.label = label.into();
+        self
+    }
+
+    /// One value for each of the chart's labels
+    pub fn data<N: Into<NumberString>>(mut self, data: impl IntoIterator<Item = N>) -> Self {
+        self.0.data = data.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// One color for each slice
+    pub fn colors<P: Into<Paint>>(mut self, colors: impl IntoIterator<Item = P>) -> Self {
+        self.0.backgroundColor = colors.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn border_color(mut self, paint: impl Into<Paint>) -> Self {
+        self.0.borderColor = paint.into();
+        self
+    }
+
+    pub fn border_width(mut self, width: impl Into<NumberString>) -> Self {
+        self.0.borderWidth = width.into();
+        self
+    }
+
+    /// For any fields that don't have a method of their own
+    pub fn with(mut self, f: impl FnOnce(&mut SinglePointDataset)) -> Self {
+        f(&mut self.0);
+        self
+    }
+}
+
+/// The options set on a scale, and the ones set that its type doesn't have
+pub struct ScaleBuilder(ChartScale, Vec<&'static str>);
+
+impl ScaleBuilder {
+    /// Changes the type of the scale, keeping the options the new type also has.
+    /// [`ChartBuilder::build`] returns an error if that drops any that were set
+    pub fn r#type(mut self, r#type: impl Into<ScaleType>) -> Self {
+        let linear_options = match &self.0 {
+            ChartScale::Auto(s) | ChartScale::Linear(s) | ChartScale::Custom(_, s) => [
+                ("beginAtZero", s.beginAtZero.is_some()),
+                ("grace", !s.grace.is_empty()),
+            ]
+            .into_iter()
+            .filter_map(|(option, set)| set.then_some(option))
+            .collect(),
+            _ => Vec::new(),
+        };
+        self.0 = self.0.with_type(r#type.into());
+        if !self.0.is_linear() {
+            self.1.extend(linear_options);
+        }
+        self
+    }
+
+    /// Only applies to linear scales, and scales without a type
+    pub fn begin_at_zero(mut self, begin_at_zero: bool) -> Self {
+        match &mut self.0 {
+            ChartScale::Auto(s) | ChartScale::Linear(s) | ChartScale::Custom(_, s) => {
+                s.beginAtZero = Some(begin_at_zero)
+            }
+            _ => self.1.push("beginAtZero"),
+        }
+        self
+    }
+
+    pub fn min(mut self, min: impl Into<NumberOrDateString>) -> Self {
+        self.0.common_mut().min = min.into();
+        self
+    }
+
+    pub fn max(mut self, max: impl Into<NumberOrDateString>) -> Self {
+        self.0.common_mut().max = max.into();
+        self
+    }
+
+    pub fn suggested_min(mut self, min: impl Into<NumberOrDateString>) -> Self {
+        self.0.common_mut().suggestedMin = min.into();
+        self
+    }
+
+    pub fn suggested_max(mut self, max: impl Into<NumberOrDateString>) -> Self {
+        self.0.common_mut().suggestedMax = max.into();
+        self
+    }
+
+    pub fn stacked(mut self, stacked: bool) -> Self {
+        self.0.common_mut().stacked = Some(stacked);
+        self
+    }
+
+    pub fn display(mut self, display: bool) -> Self {
+        self.0.common_mut().display = Some(display);
+        self
+    }
+
+    pub fn reverse(mut self, reverse: bool) -> Self {
+        self.0.common_mut().reverse = Some(reverse);
+        self
+    }
+
+    pub fn position(mut self, position: impl Into<ScalePosition>) -> Self {
+        self.0.common_mut().position = position.into();
+        self
+    }
+
+    pub fn title(mut self, text: impl Into<String>) -> Self {
+        self.0.common_mut().title = Some(Title {
+            text: text.into(),
+            display: Some(true),
+            ..Default::default()
+        });
+        self
+    }
+
+    pub fn grid(mut self, display: bool) -> Self {
+        self.0
+            .common_mut()
+            .grid
+            .get_or_insert_with(Default::default)
+            .display = Some(display);
+        self
+    }
+
+    pub fn step_size(mut self, step_size: impl Into<NumberString>) -> Self {
+        self.0.ticks_mut().stepSize = step_size.into();
+        self
+    }
+
+    /// For any fields that don't have a method of their own
+    pub fn with(mut self, f: impl FnOnce(&mut ChartScale)) -> Self {
+        f(&mut self.0);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn bar() -> ChartBuilder<Bar<NoAnnotations>> {
+        Bar::<NoAnnotations>::builder()
+            .id("sales")
+            .labels(["Q1", "Q2", "Q3"])
+    }
+
+    fn values(dataset: XYDatasetBuilder, n: usize) -> XYDatasetBuilder {
+        dataset.with(|d| d.data = DatasetData::from_serialize((1..=n).collect::<Vec<_>>()))
+    }
+
+    fn error(result: Result<impl Sized, ChartError>) -> String {
+        match result {
+            Err(ChartError::InvalidChart(e) | ChartError::InvalidColor(e)) => e,
+            Err(e) => panic!("unexpected error {e:?}"),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn builds_valid_charts() {
+        let chart = bar()
+            .dataset(|d| values(d.label("2024").color("#36a2eb"), 3))
+            .build()
+            .unwrap();
+
+        assert_eq!(chart.id, "sales");
+        assert_eq!(chart.data.datasets.len(), 1);
+    }
+
+    #[test]
+    fn charts_need_an_id() {
+        let result = Pie::<NoAnnotations>::builder()
+            .dataset(|d| d.data([1, 2]))
+            .build();
+
+        assert_eq!(error(result), "the chart has no id");
+    }
+
+    #[test]
+    fn datasets_need_their_axes() {
+        let result = bar().dataset(|d| values(d.y_axis("y2"), 3)).build();
+        assert!(error(result).contains("`y2`"));
+
+        let result = bar()
+            .scale("y2", |s| s.position("right"))
+            .dataset(|d| values(d.y_axis("y2"), 3))
+            .build();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn datasets_need_a_value_for_each_label() {
+        let result = bar().dataset(|d| values(d, 2)).build();
+        assert_eq!(error(result), "dataset 0 has 2 values for 3 labels");
+
+        let result = Doughnut::<NoAnnotations>::builder()
+            .id("browsers")
+            .labels(["Firefox", "Chrome"])
+            .dataset(|d| d.data([4, 8, 15]))
+            .build();
+        assert_eq!(error(result), "dataset 0 has 3 values for 2 labels");
+
+        // points with an x are drawn at that x, not at a label
+        let points = vec![XYPoint {
+            x: 1.into(),
+            y: 2.into(),
+            ..Default::default()
+        }];
+        assert!(bar().dataset(|d| d.data(points)).build().is_ok());
+    }
+
+    #[test]
+    fn colors_must_be_valid() {
+        let result = bar()
+            .dataset(|d| values(d.border_color("#12345"), 3))
+            .build();
+
+        assert_eq!(error(result), "#12345");
+
+        let result = bar().options(|o| o.color = "gray50".into()).build();
+        assert_eq!(error(result), "gray50");
+    }
+
+    #[test]
+    fn changing_the_scale_type_keeps_the_options_it_has() {
+        let chart = bar()
+            .scale("y", |s| {
+                s.step_size(5)
+                    .begin_at_zero(true)
+                    .position("right")
+                    .r#type("linear")
+            })
+            .scale("x", |s| s.step_size(2).r#type("time"))
+            .build()
+            .unwrap();
+        let scales = serde_json::to_value(chart.options.scales).unwrap();
+
+        assert_eq!(
+            scales["y"],
+            json!({
+                "type": "linear",
+                "position": "right",
+                "beginAtZero": true,
+                "ticks": { "stepSize": 5 }
+            })
+        );
+        assert_eq!(
+            scales["x"],
+            json!({ "type": "time", "ticks": { "stepSize": 2 } })
+        );
+    }
+
+    #[test]
+    fn linear_options_on_other_scales_are_errors() {
+        let result = bar()
+            .scale("x", |s| s.r#type("category").begin_at_zero(true))
+            .build();
+        assert_eq!(
+            error(result),
+            "`beginAtZero` only applies to linear scales, but scale `x` isn't one"
+        );
+
+        let result = bar()
+            .scale("y", |s| s.begin_at_zero(true).r#type("logarithmic"))
+            .build();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,724 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ChartError, NumberString};
+
+/// A CSS color, e.g. `"red"`, `"#ff000080"`, `"rgba(255, 0, 0, 0.5)"` or `"hsl(0, 100%, 50%)"`.
+///
+/// Like [`NumberString`](crate::NumberString), anything `Display` converts into a `Color`
+/// without being checked, so `"red".into()` keeps working in struct literals.
+/// Use [`Color::parse`], `str::parse::<Color>()` or [`Color::is_valid`] to catch typos.
+///
+/// Parsing is stricter than browsers: `rgb()` and `hsl()` take three values,
+/// `rgba()` and `hsla()` take four, so `"rgb(0, 0, 0, 0.2)"` is rejected.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Color(String);
+impl Color {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Rgba::new(r, g, b, 1.0).into()
+    }
+
+    pub fn rgba(r: u8, g: u8, b: u8, a: f64) -> Self {
+        Rgba::new(r, g, b, a).into()
+    }
+
+    /// `h` in degrees, `s` and `l` between `0.0` and `1.0`
+    pub fn hsl(h: f64, s: f64, l: f64) -> Self {
+        Rgba::from_hsla(h, s, l, 1.0).into()
+    }
+
+    /// `h` in degrees, `s`, `l` and `a` between `0.0` and `1.0`
+    pub fn hsla(h: f64, s: f64, l: f64, a: f64) -> Self {
+        Rgba::from_hsla(h, s, l, a).into()
+    }
+
+    /// Parses the color into its components
+    pub fn parse(&self) -> Result<Rgba, ChartError> {
+        self.0.parse()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.parse().is_ok()
+    }
+
+    /// Returns this color with its opacity set to `alpha`, between `0.0` and `1.0`.
+    /// Colors that can't be parsed are returned unchanged, as are the methods below
+    pub fn alpha(&self, alpha: f64) -> Self {
+        self.map(|c| Rgba {
+            a: alpha.clamp(0.0, 1.0),
+            ..c
+        })
+    }
+
+    /// Raises the lightness of this color by `amount`, between `0.0` and `1.0`
+    pub fn lighten(&self, amount: f64) -> Self {
+        self.map(|c| {
+            let (h, s, l) = c.to_hsl();
+            Rgba::from_hsla(h, s, (l + amount).clamp(0.0, 1.0), c.a)
+        })
+    }
+
+    /// Lowers the lightness of this color by `amount`, between `0.0` and `1.0`
+    pub fn darken(&self, amount: f64) -> Self {
+        self.lighten(-amount)
+    }
+
+    fn map(&self, f: impl FnOnce(Rgba) -> Rgba) -> Self {
+        match self.parse() {
+            Ok(c) => f(c).into(),
+            Err(_) => self.clone(),
+        }
+    }
+}
+impl<T: Display> From<T> for Color {
+    fn from(s: T) -> Self {
+        Self(s.to_string())
+    }
+}
+impl FromStr for Color {
+    type Err = ChartError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<Rgba>().map(|_| Self(s.to_string()))
+    }
+}
+impl Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+/// The components of a parsed [`Color`], displayed as `#rrggbb` or `rgba(r, g, b, a)`
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: f64,
+}
+impl Rgba {
+    pub fn new(r: u8, g: u8, b: u8, a: f64) -> Self {
+        Self {
+            r,
+            g,
+            b,
+            a: a.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn from_hsla(h: f64, s: f64, l: f64, a: f64) -> Self {
+        let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c / 2.0;
+        let channel = |v: f64| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+
+        Self::new(channel(r), channel(g), channel(b), a)
+    }
+
+    /// Returns the hue in degrees, and the saturation and lightness between `0.0` and `1.0`
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let (r, g, b) = (
+            self.r as f64 / 255.0,
+            self.g as f64 / 255.0,
+            self.b as f64 / 255.0,
+        );
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return (0.0, 0.0, l);
+        }
+
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            60.0 * ((g - b) / d).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / d + 2.0)
+        } else {
+            60.0 * ((r - g) / d + 4.0)
+        };
+
+        (h, s, l)
+    }
+}
+impl Display for Rgba {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.a >= 1.0 {
+            write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            let a = (self.a * 1000.0).round() / 1000.0;
+            write!(f, "rgba({}, {}, {}, {a})", self.r, self.g, self.b)
+        }
+    }
+}
+impl FromStr for Rgba {
+    type Err = ChartError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ChartError::InvalidColor(s.to_string());
+        let color = s.trim().to_ascii_lowercase();
+
+        if let Some(hex) = color.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(invalid);
+        }
+
+        if let Some((name, args)) = color
+            .strip_suffix(')')
+            .and_then(|color| color.split_once('('))
+        {
+            return parse_function(name.trim(), args).ok_or_else(invalid);
+        }
+
+        if color == "transparent" {
+            return Ok(Self::new(0, 0, 0, 0.0));
+        }
+
+        NAMED_COLORS
+            .iter()
+            .find(|(name, _)| *name == color)
+            .map(|(_, [r, g, b])| Self::new(*r, *g, *b, 1.0))
+            .ok_or_else(invalid)
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Rgba> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let digits: Vec<u8> = match hex.len() {
+        3 | 4 => hex
+            .chars()
+            .map(|c| u8::from_str_radix(&c.to_string().repeat(2), 16))
+            .collect::<Result<_, _>>()
+            .ok()?,
+        6 | 8 => (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<_, _>>()
+            .ok()?,
+        _ => return None,
+    };
+    let a = digits.get(3).map(|a| *a as f64 / 255.0).unwrap_or(1.0);
+
+    Some(Rgba::new(digits[0], digits[1], digits[2], a))
+}
+
+fn parse_function(name: &str, args: &str) -> Option<Rgba> {
+    // both `rgba(0, 0, 0, 0.5)` and `rgb(0 0 0 / 0.5)` syntaxes
+    let commas = args.contains(',');
+    let args: Vec<&str> = if commas {
+        args.split(',').map(str::trim).collect()
+    } else {
+        let (values, alpha) = match args.split_once('/') {
+            Some((values, alpha)) => (values, Some(alpha.trim())),
+            None => (args, None),
+        };
+        values.split_whitespace().chain(alpha).collect()
+    };
+
+    let alpha = match (name, args.len(), commas) {
+        ("rgb" | "hsl", 3, _) => None,
+        ("rgba" | "hsla", 4, _) | ("rgb" | "hsl", 4, false) => Some(args[3]),
+        _ => return None,
+    };
+    let a = match alpha {
+        Some(a) => parse_ratio(a, 1.0)?,
+        None => 1.0,
+    };
+
+    match name {
+        "rgb" | "rgba" => {
+            let channel = |c: &str| {
+                let v = parse_ratio(c, 255.0)?;
+                (0.0..=255.0).contains(&v).then_some(v.round() as u8)
+            };
+            Some(Rgba::new(
+                channel(args[0])?,
+                channel(args[1])?,
+                channel(args[2])?,
+                a,
+            ))
+        }
+        _ => {
+            let h = args[0].trim_end_matches("deg").parse::<f64>().ok()?;
+            let s = args[1].strip_suffix('%')?.parse::<f64>().ok()? / 100.0;
+            let l = args[2].strip_suffix('%')?.parse::<f64>().ok()? / 100.0;
+            Some(Rgba::from_hsla(h, s, l, a))
+        }
+    }
+}
+
+/// Parses a number, or a percentage of `max`
+fn parse_ratio(value: &str, max: f64) -> Option<f64> {
+    let v = match value.strip_suffix('%') {
+        Some(p) => p.parse::<f64>().ok()? / 100.0 * max,
+        None => value.parse::<f64>().ok()?,
+    };
+
+    (v.is_finite() && (0.0..=max).contains(&v)).then_some(v)
+}
+
+const NAMED_COLORS: [(&str, [u8; 3]); 148] = [
+    ("aliceblue", [240, 248, 255]),
+    ("antiquewhite", [250, 235, 215]),
+    ("aqua", [0, 255, 255]),
+    ("aquamarine", [127, 255, 212]),
+    ("azure", [240, 255, 255]),
+    ("beige", [245, 245, 220]),
+    ("bisque", [255, 228, 196]),
+    ("black", [0, 0, 0]),
+    ("blanchedalmond", [255, 235, 205]),
+    ("blue", [0, 0, 255]),
+    ("blueviolet", [138, 43, 226]),
+    ("brown", [165, 42, 42]),
+    ("burlywood", [222, 184, 135]),
+    ("cadetblue", [95, 158, 160]),
+    ("chartreuse", [127, 255, 0]),
+    ("chocolate", [210, 105, 30]),
+    ("coral", [255, 127, 80]),
+    ("cornflowerblue", [100, 149, 237]),
+    ("cornsilk", [255, 248, 220]),
+    ("crimson", [220, 20, 60]),
+    ("cyan", [0, 255, 255]),
+    ("darkblue", [0, 0, 139]),
+    ("darkcyan", [0, 139, 139]),
+    ("darkgoldenrod", [184, 134, 11]),
+    ("darkgray", [169, 169, 169]),
+    ("darkgreen", [0, 100, 0]),
+    ("darkgrey", [169, 169, 169]),
+    ("darkkhaki", [189, 183, 107]),
+    ("darkmagenta", [139, 0, 139]),
+    ("darkolivegreen", [85, 107, 47]),
+    ("darkorange", [255, 140, 0]),
+    ("darkorchid", [153, 50, 204]),
+    ("darkred", [139, 0, 0]),
+    ("darksalmon", [233, 150, 122]),
+    ("darkseagreen", [143, 188, 143]),
+    ("darkslateblue", [72, 61, 139]),
+    ("darkslategray", [47, 79, 79]),
+    ("darkslategrey", [47, 79, 79]),
+    ("darkturquoise", [0, 206, 209]),
+    ("darkviolet", [148, 0, 211]),
+    ("deeppink", [255, 20, 147]),
+    ("deepskyblue", [0, 191, 255]),
+    ("dimgray", [105, 105, 105]),
+    ("dimgrey", [105, 105, 105]),
+    ("dodgerblue", [30, 144, 255]),
+    ("firebrick", [178, 34, 34]),
+    ("floralwhite", [255, 250, 240]),
+    ("forestgreen", [34, 139, 34]),
+    ("fuchsia", [255, 0, 255]),
+    ("gainsboro", [220, 220, 220]),
+    ("ghostwhite", [248, 248, 255]),
+    ("gold", [255, 215, 0]),
+    ("goldenrod", [218, 165, 32]),
+    ("gray", [128, 128, 128]),
+    ("green", [0, 128, 0]),
+    ("greenyellow", [173, 255, 47]),
+    ("grey", [128, 128, 128]),
+    ("honeydew", [240, 255, 240]),
+    ("hotpink", [255, 105, 180]),
+    ("indianred", [205, 92, 92]),
+    ("indigo", [75, 0, 130]),
+    ("ivory", [255, 255, 240]),
+    ("khaki", [240, 230, 140]),
+    ("lavender", [230, 230, 250]),
+    ("lavenderblush", [255, 240, 245]),
+    ("lawngreen", [124, 252, 0]),
+    ("lemonchiffon", [255, 250, 205]),
+    ("lightblue", [173, 216, 230]),
+    ("lightcoral", [240, 128, 128]),
+    ("lightcyan", [224, 255, 255]),
+    ("lightgoldenrodyellow", [250, 250, 210]),
+    ("lightgray", [211, 211, 211]),
+    ("lightgreen", [144, 238, 144]),
+    ("lightgrey", [211, 211, 211]),
+    ("lightpink", [255, 182, 193]),
+    ("lightsalmon", [255, 160, 122]),
+    ("lightseagreen", [32, 178, 170]),
+    ("lightskyblue", [135, 206, 250]),
+    ("lightslategray", [119, 136, 153]),
+    ("lightslategrey", [119, 136, 153]),
+    ("lightsteelblue", [176, 196, 222]),
+    ("lightyellow", [255, 255, 224]),
+    ("lime", [0, 255, 0]),
+    ("limegreen", [50, 205, 50]),
+    ("linen", [250, 240, 230]),
+    ("magenta", [255, 0, 255]),
+    ("maroon", [128, 0, 0]),
+    ("mediumaquamarine", [102, 205, 170]),
+    ("mediumblue", [0, 0, 205]),
+    ("mediumorchid", [186, 85, 211]),
+    ("mediumpurple", [147, 112, 219]),
+    ("mediumseagreen", [60, 179, 113]),
+    ("mediumslateblue", [123, 104, 238]),
+    ("mediumspringgreen", [0, 250, 154]),
+    ("mediumturquoise", [72, 209, 204]),
+    ("mediumvioletred", [199, 21, 133]),
+    ("midnightblue", [25, 25, 112]),
+    ("mintcream", [245, 255, 250]),
+    ("mistyrose", [255, 228, 225]),
+    ("moccasin", [255, 228, 181]),
+    ("navajowhite", [255, 222, 173]),
+    ("navy", [0, 0, 128]),
+    ("oldlace", [253, 245, 230]),
+    ("olive", [128, 128, 0]),
+    ("olivedrab", [107, 142, 35]),
+    ("orange", [255, 165, 0]),
+    ("orangered", [255, 69, 0]),
+    ("orchid", [218, 112, 214]),
+    ("palegoldenrod", [238, 232, 170]),
+    ("palegreen", [152, 251, 152]),
+    ("paleturquoise", [175, 238, 238]),
+    ("palevioletred", [219, 112, 147]),
+    ("papayawhip", [255, 239, 213]),
+    ("peachpuff", [255, 218, 185]),
+    ("peru", [205, 133, 63]),
+    ("pink", [255, 192, 203]),
+    ("plum", [221, 160, 221]),
+    ("powderblue", [176, 224, 230]),
+    ("purple", [128, 0, 128]),
+    ("rebeccapurple", [102, 51, 153]),
+    ("red", [255, 0, 0]),
+    ("rosybrown", [188, 143, 143]),
+    ("royalblue", [65, 105, 225]),
+    ("saddlebrown", [139, 69, 19]),
+    ("salmon", [250, 128, 114]),
+    ("sandybrown", [244, 164, 96]),
+    ("seagreen", [46, 139, 87]),
+    ("seashell", [255, 245, 238]),
+    ("sienna", [160, 82, 45]),
+    ("silver", [192, 192, 192]),
+    ("skyblue", [135, 206, 235]),
+    ("slateblue", [106, 90, 205]),
+    ("slategray", [112, 128, 144]),
+    ("slategrey", [112, 128, 144]),
+    ("snow", [255, 250, 250]),
+    ("springgreen", [0, 255, 127]),
+    ("steelblue", [70, 130, 180]),
+    ("tan", [210, 180, 140]),
+    ("teal", [0, 128, 128]),
+    ("thistle", [216, 191, 216]),
+    ("tomato", [255, 99, 71]),
+    ("turquoise", [64, 224, 208]),
+    ("violet", [238, 130, 238]),
+    ("wheat", [245, 222, 179]),
+    ("white", [255, 255, 255]),
+    ("whitesmoke", [245, 245, 245]),
+    ("yellow", [255, 255, 0]),
+    ("yellowgreen", [154, 205, 50]),
+];
+
+/// A fill or stroke style, either a [`Color`], a [`Gradient`] or a [`Pattern`].
+///
+/// Gradients and patterns are created on the chart's canvas when it is drawn,
+/// and recreated whenever the chart area is resized.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "PaintRepr", into = "PaintRepr")]
+pub enum Paint {
+    Color(Color),
+    Gradient(Gradient),
+    Pattern(Pattern),
+}
+impl Paint {
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Paint::Color(c) if c.is_empty())
+    }
+}
+impl Default for Paint {
+    fn default() -> Self {
+        Self::Color(Color::default())
+    }
+}
+impl<T: Display> From<T> for Paint {
+    fn from(s: T) -> Self {
+        Self::Color(s.into())
+    }
+}
+impl From<Color> for Paint {
+    fn from(value: Color) -> Self {
+        Self::Color(value)
+    }
+}
+impl From<Gradient> for Paint {
+    fn from(value: Gradient) -> Self {
+        Self::Gradient(value)
+    }
+}
+impl From<Pattern> for Paint {
+    fn from(value: Pattern) -> Self {
+        Self::Pattern(value)
+    }
+}
+
+/// How a [`Paint`] is serialized, gradients and patterns are wrapped so they can be
+/// told apart from other objects when they're converted into scriptable options
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum PaintRepr {
+    Color(Color),
+    Gradient { gradient: Gradient },
+    Pattern { pattern: Pattern },
+}
+impl From<PaintRepr> for Paint {
+    fn from(value: PaintRepr) -> Self {
+        match value {
+            PaintRepr::Color(color) => Self::Color(color),
+            PaintRepr::Gradient { gradient } => Self::Gradient(gradient),
+            PaintRepr::Pattern { pattern } => Self::Pattern(pattern),
+        }
+    }
+}
+impl From<Paint> for PaintRepr {
+    fn from(value: Paint) -> Self {
+        match value {
+            Paint::Color(color) => Self::Color(color),
+            Paint::Gradient(gradient) => Self::Gradient { gradient },
+            Paint::Pattern(pattern) => Self::Pattern { pattern },
+        }
+    }
+}
+
+/// A linear or radial gradient, e.g. an area fill fading to transparent:
+/// ```rust,ignore
+///   Gradient::linear(GradientDirection::ToTop)
+///     .stop(0, Color::from("dodgerblue").alpha(0.0))
+///     .stop(1, "dodgerblue")
+/// ```
+///
+/// Stop offsets are between `0` and `1` across the chart area, or are values on a scale
+/// when [`Gradient::scale`] is set, e.g. to turn a line red above `100` on the `y` scale
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Gradient {
+    #[serde(rename = "type")]
+    pub r#type: GradientType,
+
+    /// Only used by linear gradients that aren't relative to a scale
+    pub direction: GradientDirection,
+
+    /// The id of the scale the stop offsets are values of
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub scale: String,
+
+    pub stops: Vec<ColorStop>,
+}
+impl Gradient {
+    pub fn linear(direction: GradientDirection) -> Self {
+        Self {
+            r#type: GradientType::Linear,
+            direction,
+            ..Default::default()
+        }
+    }
+
+    /// A gradient outwards from the center of the chart area,
+    /// or of the scale for radial scales like those of polar area and radar charts
+    pub fn radial() -> Self {
+        Self {
+            r#type: GradientType::Radial,
+            ..Default::default()
+        }
+    }
+
+    pub fn stop(mut self, offset: impl Into<NumberString>, color: impl Into<Color>) -> Self {
+        self.stops.push(ColorStop {
+            offset: offset.into(),
+            color: color.into(),
+        });
+        self
+    }
+
+    pub fn scale(mut self, scale_id: &str) -> Self {
+        self.scale = scale_id.to_string();
+        self
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum GradientType {
+    #[default]
+    Linear,
+    Radial,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum GradientDirection {
+    #[default]
+    ToTop,
+    ToBottom,
+    ToLeft,
+    ToRight,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ColorStop {
+    pub offset: NumberString,
+    pub color: Color,
+}
+
+/// A repeating pattern, e.g. to tell series apart without relying on color alone
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Pattern {
+    pub shape: PatternShape,
+
+    pub color: Color,
+
+    /// Transparent when empty
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub background: Color,
+
+    /// The width and height of a tile of the pattern in pixels, `10` when empty
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub size: NumberString,
+
+    /// The width of the lines, or the radius of the dots, `1` when empty
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub lineWidth: NumberString,
+}
+impl Pattern {
+    pub fn new(shape: PatternShape, color: impl Into<Color>) -> Self {
+        Self {
+            shape,
+            color: color.into(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum PatternShape {
+    #[default]
+    Diagonal,
+    ReverseDiagonal,
+    Horizontal,
+    Vertical,
+    Cross,
+    Dots,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgba(s: &str) -> Rgba {
+        s.parse().unwrap_or_else(|_| panic!("`{s}` should parse"))
+    }
+
+    #[test]
+    fn hex() {
+        assert_eq!(rgba("#f80"), Rgba::new(255, 136, 0, 1.0));
+        assert_eq!(rgba("#f808"), Rgba::new(255, 136, 0, 136.0 / 255.0));
+        assert_eq!(rgba("#1E90FF"), Rgba::new(30, 144, 255, 1.0));
+        assert_eq!(rgba("#1e90ff80"), Rgba::new(30, 144, 255, 128.0 / 255.0));
+
+        for invalid in ["#", "#ff", "#fffff", "#ggg", "#1e90ff8"] {
+            assert!(!Color::from(invalid).is_valid(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn rgb_functions() {
+        let expected = Rgba::new(30, 144, 255, 1.0);
+        assert_eq!(rgba("rgb(30, 144, 255)"), expected);
+        assert_eq!(rgba("rgb(30 144 255)"), expected);
+        assert_eq!(rgba(" RGB(30,144,255) "), expected);
+        assert_eq!(rgba("rgb(0%, 100%, 50%)"), Rgba::new(0, 255, 128, 1.0));
+
+        let translucent = Rgba::new(30, 144, 255, 0.5);
+        assert_eq!(rgba("rgba(30, 144, 255, 0.5)"), translucent);
+        assert_eq!(rgba("rgb(30 144 255 / 0.5)"), translucent);
+        assert_eq!(rgba("rgba(30 144 255 / 50%)"), translucent);
+    }
+
+    #[test]
+    fn hsl_functions() {
+        let red = Rgba::new(255, 0, 0, 1.0);
+        assert_eq!(rgba("hsl(0, 100%, 50%)"), red);
+        assert_eq!(rgba("hsl(360deg 100% 50%)"), red);
+        assert_eq!(rgba("hsl(210, 100%, 56%)"), Rgba::new(31, 143, 255, 1.0));
+        assert_eq!(rgba("hsla(120, 100%, 25%, 0.2)"), Rgba::new(0, 128, 0, 0.2));
+        assert_eq!(rgba("hsl(120 100% 25% / 0.2)"), Rgba::new(0, 128, 0, 0.2));
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(rgba("dodgerblue"), Rgba::new(30, 144, 255, 1.0));
+        assert_eq!(rgba("RebeccaPurple"), Rgba::new(102, 51, 153, 1.0));
+        assert_eq!(rgba("transparent"), Rgba::new(0, 0, 0, 0.0));
+        assert!(!Color::from("bleu").is_valid());
+    }
+
+    #[test]
+    fn rejects_wrong_argument_counts() {
+        for invalid in [
+            "rgb(0, 0, 0, 0.2)",
+            "rgba(0, 0, 0)",
+            "hsl(0, 100%, 50%, 0.5)",
+            "rgb(0, 0)",
+            "rgb(256, 0, 0)",
+            "hsl(0, 100, 50)",
+            "rgb(0, 0, 0",
+        ] {
+            assert_eq!(
+                invalid.parse::<Color>(),
+                Err(ChartError::InvalidColor(invalid.into())),
+            );
+        }
+    }
+
+    #[test]
+    fn hsl_round_trips() {
+        for color in ["#1e90ff", "#ff0000", "#808080", "#663399"] {
+            let c = rgba(color);
+            let (h, s, l) = c.to_hsl();
+            assert_eq!(Rgba::from_hsla(h, s, l, 1.0), c, "{color}");
+        }
+        assert_eq!(Color::from("#808080").lighten(0.1).as_str(), "#9a9a9a");
+        assert_eq!(
+            Color::from("red").alpha(0.25).as_str(),
+            "rgba(255, 0, 0, 0.25)"
+        );
+        assert_eq!(Color::from("bleu").darken(0.1).as_str(), "bleu");
+    }
+}
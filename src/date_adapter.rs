@@ -0,0 +1,281 @@
+use std::sync::Once;
+
+use js_sys::{Date, Object, Reflect};
+use wasm_bindgen::{closure::Closure, prelude::wasm_bindgen, JsCast, JsValue};
+
+use crate::{dates::DateParts, types::TimeUnit};
+
+/// Registers a date adapter written in Rust, so time scales work without loading
+/// `chartjs-adapter-date-fns` or another adapter. Dates are shown in the browser's time zone,
+/// with English month and day names, and strings are parsed with `Date.parse`, so they
+/// should be ISO 8601. Formats use the tokens of date-fns, e.g. `MMM d, HH:mm`.
+///
+/// Rendering or updating a chart calls this when it has a time scale but no adapter is
+/// registered, and calling it more than once does nothing.
+pub fn register_date_adapter() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        let adapter = Object::new();
+        let set = |name: &str, f: JsValue| {
+            Reflect::set(&adapter, &name.into(), &f).unwrap();
+        };
+
+        set(
+            "formats",
+            Closure::<dyn Fn() -> JsValue>::new(formats).into_js_value(),
+        );
+        set(
+            "parse",
+            Closure::<dyn Fn(JsValue) -> JsValue>::new(parse).into_js_value(),
+        );
+        set(
+            "format",
+            Closure::<dyn Fn(f64, String) -> String>::new(|time, format: String| {
+                DateParts::of(time).format(&format)
+            })
+            .into_js_value(),
+        );
+        set(
+            "add",
+            Closure::<dyn Fn(f64, f64, String) -> f64>::new(|time, amount, unit: String| {
+                add(time, amount, &unit.into())
+            })
+            .into_js_value(),
+        );
+        set(
+            "diff",
+            Closure::<dyn Fn(f64, f64, String) -> f64>::new(|max, min, unit: String| {
+                diff(max, min, &unit.into())
+            })
+            .into_js_value(),
+        );
+        set(
+            "startOf",
+            Closure::<dyn Fn(f64, String, JsValue) -> f64>::new(
+                |time, unit: String, weekday: JsValue| {
+                    let week_start = match weekday.as_bool() {
+                        Some(true) => 1,
+                        _ => weekday.as_f64().unwrap_or(0.0) as i32,
+                    };
+                    start_of(time, &unit.into(), week_start)
+                },
+            )
+            .into_js_value(),
+        );
+        set(
+            "endOf",
+            Closure::<dyn Fn(f64, String) -> f64>::new(|time, unit: String| {
+                let unit = unit.into();
+                match unit {
+                    TimeUnit::Custom(ref u) if u != "isoWeek" => time,
+                    _ => start_of(add(start_of(time, &unit, 0), 1.0, &unit), &unit, 0) - 1.0,
+                }
+            })
+            .into_js_value(),
+        );
+
+        override_date_adapter(adapter);
+    });
+}
+
+fn formats() -> JsValue {
+    let formats = Object::new();
+    [
+        ("datetime", "MMM d, yyyy, h:mm:ss a"),
+        ("millisecond", "h:mm:ss.SSS a"),
+        ("second", "h:mm:ss a"),
+        ("minute", "h:mm a"),
+        ("hour", "ha"),
+        ("day", "MMM d"),
+        ("week", "MMM d"),
+        ("month", "MMM yyyy"),
+        ("quarter", "'Q'q - yyyy"),
+        ("year", "yyyy"),
+    ]
+    .into_iter()
+    .for_each(|(unit, format)| {
+        Reflect::set(&formats, &unit.into(), &format.into()).unwrap();
+    });
+
+    formats.into()
+}
+
+fn parse(value: JsValue) -> JsValue {
+    let time = if let Some(time) = value.as_f64() {
+        time
+    } else if let Some(date) = value.dyn_ref::<Date>() {
+        date.get_time()
+    } else if let Some(s) = value.as_string() {
+        Date::parse(&s)
+    } else {
+        f64::NAN
+    };
+
+    if time.is_finite() {
+        time.into()
+    } else {
+        JsValue::NULL
+    }
+}
+
+/// The length of the units that don't depend on the calendar
+fn fixed_millis(unit: &TimeUnit) -> Option<f64> {
+    match unit {
+        TimeUnit::Millisecond => Some(1.0),
+        TimeUnit::Second => Some(1000.0),
+        TimeUnit::Minute => Some(60.0 * 1000.0),
+        TimeUnit::Hour => Some(60.0 * 60.0 * 1000.0),
+        _ => None,
+    }
+}
+
+fn add(time: f64, amount: f64, unit: &TimeUnit) -> f64 {
+    if let Some(millis) = fixed_millis(unit) {
+        return time + amount * millis;
+    }
+
+    let amount = amount.trunc() as i32;
+    let mut parts = DateParts::of(time);
+    match unit {
+        TimeUnit::Day => parts.day += amount,
+        TimeUnit::Month => parts.add_months(amount),
+        TimeUnit::Quarter => parts.add_months(amount * 3),
+        TimeUnit::Year => parts.add_months(amount * 12),
+        TimeUnit::Week => parts.day += amount * 7,
+        TimeUnit::Custom(unit) if unit == "isoWeek" => parts.day += amount * 7,
+        _ => return time,
+    }
+    parts.time()
+}
+
+fn diff(max: f64, min: f64, unit: &TimeUnit) -> f64 {
+    if let Some(millis) = fixed_millis(unit) {
+        return ((max - min) / millis).trunc();
+    }
+
+    let months = |max: f64, min: f64| {
+        let (a, b) = (DateParts::of(max), DateParts::of(min));
+        let mut months = (a.year - b.year) * 12 + a.month - b.month;
+        // only count months that have fully passed
+        if months > 0 && add(min, months as f64, &TimeUnit::Month) > max {
+            months -= 1;
+        } else if months < 0 && add(min, months as f64, &TimeUnit::Month) < max {
+            months += 1;
+        }
+        months
+    };
+
+    match unit {
+        TimeUnit::Month => months(max, min) as f64,
+        TimeUnit::Quarter => (months(max, min) / 3) as f64,
+        TimeUnit::Year => (months(max, min) / 12) as f64,
+        TimeUnit::Day => {
+            // days in local time are 23 or 25 hours long when the clocks change
+            let (a, b) = (DateParts::of(max), DateParts::of(min));
+            let days = |p: &DateParts| {
+                Date::utc(p.year as f64, p.month as f64) + (p.day as f64 - 1.0) * 86_400_000.0
+            };
+            ((days(&a) - days(&b)) / 86_400_000.0).trunc()
+        }
+        TimeUnit::Week => (diff(max, min, &TimeUnit::Day) / 7.0).trunc(),
+        TimeUnit::Custom(unit) if unit == "isoWeek" => {
+            (diff(max, min, &TimeUnit::Day) / 7.0).trunc()
+        }
+        _ => 0.0,
+    }
+}
+
+fn start_of(time: f64, unit: &TimeUnit, week_start: i32) -> f64 {
+    let mut parts = DateParts::of(time);
+    match unit {
+        TimeUnit::Millisecond => return time,
+        TimeUnit::Second => parts.millisecond = 0,
+        TimeUnit::Minute => parts.truncate(2),
+        TimeUnit::Hour => parts.truncate(3),
+        TimeUnit::Day => parts.truncate(4),
+        TimeUnit::Week => {
+            parts.truncate(4);
+            parts.day -= parts.weekday;
+        }
+        TimeUnit::Month => {
+            parts.truncate(4);
+            parts.day = 1;
+        }
+        TimeUnit::Quarter => {
+            parts.truncate(4);
+            parts.day = 1;
+            parts.month -= parts.month % 3;
+        }
+        TimeUnit::Year => {
+            parts.truncate(4);
+            parts.day = 1;
+            parts.month = 0;
+        }
+        // starting on `week_start`, from 0 for Sunday to 6 for Saturday
+        TimeUnit::Custom(unit) if unit == "isoWeek" => {
+            parts.truncate(4);
+            parts.day -= (parts.weekday - week_start % 7 + 7) % 7;
+        }
+        TimeUnit::Custom(_) => return time,
+    }
+    parts.time()
+}
+
+/// Times in the browser's time zone
+impl DateParts {
+    fn of(time: f64) -> Self {
+        let date = Date::new(&time.into());
+        Self {
+            year: date.get_full_year() as i32,
+            month: date.get_month() as i32,
+            day: date.get_date() as i32,
+            hour: date.get_hours() as i32,
+            minute: date.get_minutes() as i32,
+            second: date.get_seconds() as i32,
+            millisecond: date.get_milliseconds() as i32,
+            weekday: date.get_day() as i32,
+        }
+    }
+
+    /// Out of range fields roll over into the next larger unit, like `new Date(..)`
+    fn time(&self) -> f64 {
+        Date::new_with_year_month_day_hr_min_sec_milli(
+            self.year as u32,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+            self.millisecond,
+        )
+        .get_time()
+    }
+
+    /// Zeroes the `count` smallest fields, from milliseconds up to hours
+    fn truncate(&mut self, count: usize) {
+        [
+            &mut self.millisecond,
+            &mut self.second,
+            &mut self.minute,
+            &mut self.hour,
+        ]
+        .into_iter()
+        .take(count)
+        .for_each(|field| *field = 0);
+    }
+
+    /// Adds months, keeping the day within the new month, e.g. Jan 31st + 1 month is Feb 28th
+    fn add_months(&mut self, months: i32) {
+        self.month += months;
+        let last_day =
+            Date::new_with_year_month_day(self.year as u32, self.month + 1, 0).get_date();
+        self.day = self.day.min(last_day as i32);
+    }
+}
+
+#[wasm_bindgen(inline_js = r#"export function override_date_adapter(adapter) {
+    Chart._adapters._date.override(adapter);
+}"#)]
+extern "C" {
+    fn override_date_adapter(adapter: Object);
+}
@@ -0,0 +1,465 @@
+use crate::types::{DisplayFormats, NumberOrDateString, ScaleTime, TimeUnit};
+
+/// A date or time that can be put on a time scale, implemented for `js_sys::Date`,
+/// and for the types of `chrono` and `time` with those features.
+/// Dates and times without a time zone are taken to be UTC.
+///
+/// ```rust,ignore
+///   XYPoint {
+///       x: NumberOrDateString::from_date(&reading.taken_at),
+///       y: reading.value.into(),
+///       ..Default::default()
+///   }
+/// ```
+pub trait ChartDate {
+    /// Milliseconds since the Unix epoch
+    fn timestamp_millis(&self) -> i64;
+
+    /// An ISO 8601 string in UTC, e.g. `2024-01-31T09:30:00.000Z`
+    fn to_iso_string(&self) -> String;
+}
+
+impl NumberOrDateString {
+    /// A date as milliseconds since the Unix epoch, which every date adapter reads the same way
+    pub fn from_date(date: &impl ChartDate) -> Self {
+        date.timestamp_millis().into()
+    }
+
+    /// A date as an ISO 8601 string, which is easier to read in the generated config
+    pub fn from_iso_date(date: &impl ChartDate) -> Self {
+        date.to_iso_string().into()
+    }
+}
+
+/// Used by `#[derive(ChartData)]` to put dates on a chart with [`NumberOrDateString::from_date`],
+/// and anything else with `Display`. Method resolution tries `DateValue`'s `&T` receiver before
+/// `DisplayValue`'s `&&T`, so dates such as `chrono::DateTime`, which are also `Display`,
+/// don't end up as text like `2024-01-01 00:00:00 UTC`
+#[doc(hidden)]
+pub mod derive_support {
+    use std::fmt::Display;
+
+    use super::ChartDate;
+    use crate::types::NumberOrDateString;
+
+    pub trait DateValue {
+        fn chart_value(&self) -> NumberOrDateString;
+    }
+
+    impl<T: ChartDate> DateValue for T {
+        fn chart_value(&self) -> NumberOrDateString {
+            NumberOrDateString::from_date(self)
+        }
+    }
+
+    pub trait DisplayValue {
+        fn chart_value(&self) -> NumberOrDateString;
+    }
+
+    impl<T: Display + ?Sized> DisplayValue for &T {
+        fn chart_value(&self) -> NumberOrDateString {
+            self.to_string().into()
+        }
+    }
+}
+
+impl ChartDate for js_sys::Date {
+    fn timestamp_millis(&self) -> i64 {
+        self.get_time() as i64
+    }
+
+    fn to_iso_string(&self) -> String {
+        js_sys::Date::to_iso_string(self).into()
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+
+    use super::ChartDate;
+
+    impl<Tz: TimeZone> ChartDate for DateTime<Tz> {
+        fn timestamp_millis(&self) -> i64 {
+            DateTime::timestamp_millis(self)
+        }
+
+        fn to_iso_string(&self) -> String {
+            self.with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Millis, true)
+        }
+    }
+
+    impl ChartDate for NaiveDateTime {
+        fn timestamp_millis(&self) -> i64 {
+            self.and_utc().timestamp_millis()
+        }
+
+        fn to_iso_string(&self) -> String {
+            self.and_utc().to_iso_string()
+        }
+    }
+
+    impl ChartDate for NaiveDate {
+        fn timestamp_millis(&self) -> i64 {
+            self.and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+                .timestamp_millis()
+        }
+
+        fn to_iso_string(&self) -> String {
+            self.and_hms_opt(0, 0, 0).unwrap().and_utc().to_iso_string()
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::{Date, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+    use super::ChartDate;
+
+    impl ChartDate for OffsetDateTime {
+        fn timestamp_millis(&self) -> i64 {
+            (self.unix_timestamp_nanos() / 1_000_000) as i64
+        }
+
+        fn to_iso_string(&self) -> String {
+            let utc = self.to_offset(UtcOffset::UTC);
+            format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+                utc.year(),
+                utc.month() as u8,
+                utc.day(),
+                utc.hour(),
+                utc.minute(),
+                utc.second(),
+                utc.millisecond()
+            )
+        }
+    }
+
+    impl ChartDate for PrimitiveDateTime {
+        fn timestamp_millis(&self) -> i64 {
+            self.assume_utc().timestamp_millis()
+        }
+
+        fn to_iso_string(&self) -> String {
+            self.assume_utc().to_iso_string()
+        }
+    }
+
+    impl ChartDate for Date {
+        fn timestamp_millis(&self) -> i64 {
+            self.midnight().timestamp_millis()
+        }
+
+        fn to_iso_string(&self) -> String {
+            self.midnight().to_iso_string()
+        }
+    }
+}
+
+/// The fewest ticks that [`TimeUnit::for_span`] picks a unit for
+const MIN_TICKS: i64 = 6;
+
+impl TimeUnit {
+    /// The largest unit that shows a span of this many milliseconds in at least 6 ticks
+    pub fn for_span(millis: i64) -> Self {
+        [
+            Self::Year,
+            Self::Quarter,
+            Self::Month,
+            Self::Week,
+            Self::Day,
+            Self::Hour,
+            Self::Minute,
+            Self::Second,
+        ]
+        .into_iter()
+        .find(|unit| millis.abs() / unit.approx_millis() >= MIN_TICKS)
+        .unwrap_or(Self::Millisecond)
+    }
+
+    /// The largest unit that shows the dates from `start` to `end` in at least 6 ticks
+    pub fn for_range(start: &impl ChartDate, end: &impl ChartDate) -> Self {
+        Self::for_span(end.timestamp_millis() - start.timestamp_millis())
+    }
+
+    fn approx_millis(&self) -> i64 {
+        const DAY: i64 = 24 * 60 * 60 * 1000;
+        match self {
+            Self::Millisecond => 1,
+            Self::Second => 1000,
+            Self::Minute => 60 * 1000,
+            Self::Hour => 60 * 60 * 1000,
+            Self::Day => DAY,
+            Self::Week => 7 * DAY,
+            Self::Month => 30 * DAY,
+            Self::Quarter => 91 * DAY,
+            Self::Year | Self::Custom(_) => 365 * DAY,
+        }
+    }
+}
+
+impl ScaleTime {
+    /// A `unit` and `displayFormats` for a span of this many milliseconds, which
+    /// add the day or year to labels when the span crosses them.
+    /// The formats use tokens that `chartjs-adapter-date-fns` and `chartjs-adapter-luxon` share
+    pub fn for_span(millis: i64) -> Self {
+        let unit = TimeUnit::for_span(millis);
+        let span = millis.abs();
+        let days = span > TimeUnit::Day.approx_millis();
+        let years = span > TimeUnit::Year.approx_millis();
+
+        let format = match &unit {
+            TimeUnit::Millisecond => "HH:mm:ss.SSS",
+            TimeUnit::Second => "HH:mm:ss",
+            TimeUnit::Minute | TimeUnit::Hour if days => "MMM d, HH:mm",
+            TimeUnit::Minute | TimeUnit::Hour => "HH:mm",
+            TimeUnit::Day | TimeUnit::Week if years => "MMM d, yyyy",
+            TimeUnit::Day | TimeUnit::Week => "MMM d",
+            TimeUnit::Month | TimeUnit::Quarter => "MMM yyyy",
+            TimeUnit::Year | TimeUnit::Custom(_) => "yyyy",
+        }
+        .to_string();
+
+        let mut displayFormats = DisplayFormats::default();
+        *match &unit {
+            TimeUnit::Millisecond => &mut displayFormats.millisecond,
+            TimeUnit::Second => &mut displayFormats.second,
+            TimeUnit::Minute => &mut displayFormats.minute,
+            TimeUnit::Hour => &mut displayFormats.hour,
+            TimeUnit::Day => &mut displayFormats.day,
+            TimeUnit::Week => &mut displayFormats.week,
+            TimeUnit::Month => &mut displayFormats.month,
+            TimeUnit::Quarter => &mut displayFormats.quarter,
+            TimeUnit::Year | TimeUnit::Custom(_) => &mut displayFormats.year,
+        } = format;
+
+        ScaleTime {
+            displayFormats: Some(displayFormats),
+            unit,
+            ..Default::default()
+        }
+    }
+
+    /// A `unit` and `displayFormats` for the dates from `start` to `end`, see [`ScaleTime::for_span`]
+    pub fn for_range(start: &impl ChartDate, end: &impl ChartDate) -> Self {
+        Self::for_span(end.timestamp_millis() - start.timestamp_millis())
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// The fields of a date, for formatting it with date-fns tokens
+pub(crate) struct DateParts {
+    pub(crate) year: i32,
+    /// From 0 for January
+    pub(crate) month: i32,
+    pub(crate) day: i32,
+    pub(crate) hour: i32,
+    pub(crate) minute: i32,
+    pub(crate) second: i32,
+    pub(crate) millisecond: i32,
+    /// From 0 for Sunday
+    pub(crate) weekday: i32,
+}
+
+impl DateParts {
+    /// The date in UTC, `millis` after the Unix epoch
+    pub(crate) fn from_utc_millis(millis: i64) -> Self {
+        const DAY: i64 = 24 * 60 * 60 * 1000;
+        let (days, time) = (millis.div_euclid(DAY), millis.rem_euclid(DAY));
+
+        // the civil calendar from a day count, as in http://howardhinnant.github.io/date_algorithms.html
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let month = if mp < 10 { mp + 2 } else { mp - 10 };
+        let year = yoe + era * 400 + i64::from(month <= 1);
+
+        Self {
+            year: year as i32,
+            month: month as i32,
+            day: (doy - (153 * mp + 2) / 5 + 1) as i32,
+            hour: (time / 3_600_000) as i32,
+            minute: (time / 60_000 % 60) as i32,
+            second: (time / 1000 % 60) as i32,
+            millisecond: (time % 1000) as i32,
+            // the epoch was a Thursday
+            weekday: (days + 4).rem_euclid(7) as i32,
+        }
+    }
+
+    /// Formats the date with the tokens of date-fns, e.g. `MMM d, HH:mm`.
+    /// Text in single quotes is kept as it is, and months and days are in English
+    pub(crate) fn format(&self, format: &str) -> String {
+        let mut out = String::new();
+        let mut chars = format.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                // quoted text, where '' is a quote
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                    out.push('\'');
+                    continue;
+                }
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\'' if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                            out.push('\'');
+                        }
+                        '\'' => break,
+                        c => out.push(c),
+                    }
+                }
+                continue;
+            }
+
+            let mut count = 1;
+            while chars.peek() == Some(&c) {
+                chars.next();
+                count += 1;
+            }
+
+            let hour12 = (self.hour + 11) % 12 + 1;
+            let token = match c {
+                'y' if count == 2 => format!("{:02}", self.year % 100),
+                'y' => format!("{:0count$}", self.year),
+                'q' | 'Q' => format!("{}", self.month / 3 + 1),
+                'M' | 'L' if count == 3 => MONTHS[self.month as usize][..3].to_string(),
+                'M' | 'L' if count >= 4 => MONTHS[self.month as usize].to_string(),
+                'M' | 'L' => format!("{:0count$}", self.month + 1),
+                'd' => format!("{:0count$}", self.day),
+                'E' if count >= 4 => WEEKDAYS[self.weekday as usize].to_string(),
+                'E' => WEEKDAYS[self.weekday as usize][..3].to_string(),
+                'H' => format!("{:0count$}", self.hour),
+                'h' => format!("{:0count$}", hour12),
+                'm' => format!("{:0count$}", self.minute),
+                's' => format!("{:0count$}", self.second),
+                'S' => format!("{:03}", self.millisecond)
+                    .chars()
+                    .chain(std::iter::repeat('0'))
+                    .take(count)
+                    .collect(),
+                'a' if self.hour < 12 => "AM".to_string(),
+                'a' => "PM".to_string(),
+                c => c.to_string().repeat(count),
+            };
+            out.push_str(&token);
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 60 * 60 * 1000;
+    const DAY: i64 = 24 * HOUR;
+
+    #[test]
+    fn time_units_for_spans() {
+        assert_eq!(TimeUnit::for_span(500), TimeUnit::Millisecond);
+        assert_eq!(TimeUnit::for_span(6_000), TimeUnit::Second);
+        assert_eq!(TimeUnit::for_span(5 * 60_000), TimeUnit::Second);
+        assert_eq!(TimeUnit::for_span(HOUR), TimeUnit::Minute);
+        assert_eq!(TimeUnit::for_span(DAY), TimeUnit::Hour);
+        assert_eq!(TimeUnit::for_span(10 * DAY), TimeUnit::Day);
+        assert_eq!(TimeUnit::for_span(60 * DAY), TimeUnit::Week);
+        assert_eq!(TimeUnit::for_span(365 * DAY), TimeUnit::Month);
+        assert_eq!(TimeUnit::for_span(2 * 365 * DAY), TimeUnit::Quarter);
+        assert_eq!(TimeUnit::for_span(10 * 365 * DAY), TimeUnit::Year);
+        // backwards ranges are as long as forwards ones
+        assert_eq!(TimeUnit::for_span(-10 * DAY), TimeUnit::Day);
+    }
+
+    #[test]
+    fn scale_time_for_spans() {
+        let one_day = ScaleTime::for_span(DAY);
+        assert_eq!(one_day.unit, TimeUnit::Hour);
+        assert_eq!(one_day.displayFormats.unwrap().hour, "HH:mm");
+
+        let two_days = ScaleTime::for_span(2 * DAY);
+        assert_eq!(two_days.displayFormats.unwrap().hour, "MMM d, HH:mm");
+
+        let two_years = ScaleTime::for_span(2 * 365 * DAY);
+        assert_eq!(two_years.unit, TimeUnit::Quarter);
+        assert_eq!(two_years.displayFormats.unwrap().quarter, "MMM yyyy");
+    }
+
+    #[test]
+    fn date_parts_from_millis() {
+        let epoch = DateParts::from_utc_millis(0);
+        assert_eq!(
+            (epoch.year, epoch.month, epoch.day, epoch.weekday),
+            (1970, 0, 1, 4)
+        );
+
+        // 2024-02-29T13:05:09.007Z, a Thursday in a leap year
+        let leap = DateParts::from_utc_millis(1_709_211_909_007);
+        assert_eq!((leap.year, leap.month, leap.day), (2024, 1, 29));
+        assert_eq!(
+            (leap.hour, leap.minute, leap.second, leap.millisecond),
+            (13, 5, 9, 7)
+        );
+        assert_eq!(leap.weekday, 4);
+
+        // the last millisecond before the epoch
+        let before = DateParts::from_utc_millis(-1);
+        assert_eq!(
+            (before.year, before.month, before.day, before.hour),
+            (1969, 11, 31, 23)
+        );
+        assert_eq!(before.millisecond, 999);
+    }
+
+    #[test]
+    fn date_parts_format() {
+        let date = DateParts::from_utc_millis(1_709_211_909_007);
+        assert_eq!(
+            date.format("yyyy-MM-dd HH:mm:ss.SSS"),
+            "2024-02-29 13:05:09.007"
+        );
+        assert_eq!(date.format("MMM d, yy"), "Feb 29, 24");
+        assert_eq!(date.format("EEEE, MMMM d"), "Thursday, February 29");
+        assert_eq!(date.format("EEE h:mm a"), "Thu 1:05 PM");
+        assert_eq!(date.format("hh a"), "01 PM");
+        assert_eq!(date.format("'Q'q yyyy"), "Q1 2024");
+        assert_eq!(date.format("'o''clock' H"), "o'clock 13");
+        assert_eq!(date.format("S"), "0");
+
+        let midnight = DateParts::from_utc_millis(0);
+        assert_eq!(midnight.format("h a"), "12 AM");
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use gloo_utils::format::JsValueSerdeExt;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+use crate::{types::*, utils::js_error, ChartError, Color};
+
+/// Options shared by every chart on the page, set once with [`ChartDefaults::set`]
+/// before any chart is rendered, so they don't need to be repeated in each chart's options.
+///
+/// ```rust,ignore
+///   ChartDefaults {
+///       font: Some(Font {
+///           family: "Inter, sans-serif".into(),
+///           ..Default::default()
+///       }),
+///       maintainAspectRatio: Some(false),
+///       ..Default::default()
+///   }
+///   .set()?;
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
+pub struct ChartDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font: Option<Font>,
+
+    /// The default color of text
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub color: Color,
+
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub backgroundColor: Color,
+
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub borderColor: Color,
+
+    /// The language numbers are formatted in
+    #[serde(skip_serializing_if = "Locale::is_empty", default)]
+    pub locale: Locale,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responsive: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maintainAspectRatio: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animation: Option<Animation>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interaction: Option<ChartInteraction>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elements: Option<ChartElements>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugins: Option<ChartPlugins<NoAnnotations>>,
+
+    /// The defaults of every scale
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<ChartScale>,
+
+    /// The defaults of each type of scale, e.g. `linear` or `time`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scales: Option<HashMap<String, ChartScale>>,
+}
+
+impl ChartDefaults {
+    /// Merges these into `Chart.defaults`, leaving any defaults that aren't set here as they are.
+    /// Charts that are already rendered are not affected
+    pub fn set(&self) -> Result<(), ChartError> {
+        set_defaults(to_js(self)?).map_err(js_error)
+    }
+}
+
+/// Merges `options` into `Chart.overrides[chart_type]`, the defaults of one type of chart,
+/// e.g. `"bar"` or `"doughnut"`, which take precedence over [`ChartDefaults`]
+pub fn set_overrides<A: Annotation>(
+    chart_type: &str,
+    options: &ChartOptions<A>,
+) -> Result<(), ChartError> {
+    set_chart_overrides(chart_type, to_js(options)?).map_err(js_error)
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, ChartError> {
+    <JsValue as JsValueSerdeExt>::from_serde(value).map_err(|e| ChartError::Serde(e.to_string()))
+}
+
+#[wasm_bindgen(inline_js = r#"function merge(target, source) {
+    Object.keys(source).forEach(key => {
+        const value = source[key];
+        if (value && typeof value === 'object' && !Array.isArray(value)
+            && target[key] && typeof target[key] === 'object' && !Array.isArray(target[key])) {
+            merge(target[key], value);
+        } else {
+            target[key] = value;
+        }
+    });
+
+    return target;
+}
+
+export function set_defaults(defaults) {
+    Chart.defaults.set(defaults);
+}
+
+export function set_chart_overrides(type, options) {
+    Chart.overrides[type] = merge(Chart.overrides[type] || {}, options);
+}"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn set_defaults(defaults: JsValue) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch)]
+    fn set_chart_overrides(chart_type: &str, options: JsValue) -> Result<(), JsValue>;
+}
@@ -0,0 +1,30 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ChartError {
+    #[error("No chart is rendered on the element with id `{0}`")]
+    ChartNotFound(String),
+
+    #[error("Chart.js threw an error: {0}")]
+    JsError(String),
+
+    #[error("`{0}` is not a valid CSS color")]
+    InvalidColor(String),
+
+    #[error("Invalid chart: {0}")]
+    InvalidChart(String),
+
+    #[error(
+        "Time scales need a date adapter, e.g. `chartjs-adapter-date-fns`, but none is registered"
+    )]
+    MissingDateAdapter,
+
+    #[error("Unable to (de)serialize chart value: {0}")]
+    Serde(String),
+}
+
+impl From<serde_wasm_bindgen::Error> for ChartError {
+    fn from(value: serde_wasm_bindgen::Error) -> Self {
+        Self::Serde(value.to_string())
+    }
+}
@@ -0,0 +1,424 @@
+use crate::{dates::DateParts, utils::FnWithArgs};
+
+/// A reusable way of turning numbers, or dates, into text, which compiles to the callbacks
+/// of ticks, tooltips and data labels, and can also be run in Rust with [`Formatter::format`].
+///
+/// In the browser, numbers use the separators of the chart's `locale`, or the browser's.
+/// In Rust they use `,` and `.`, and dates are in UTC.
+///
+/// ```rust,ignore
+///   ScaleTicks {
+///       callback: Formatter::Currency { currency: "USD".into(), decimals: 2 }.tick_callback(),
+///       ..Default::default()
+///   }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Formatter {
+    /// `1234.5` as `$1,234.50`, with an ISO 4217 code, e.g. `USD`
+    Currency { currency: String, decimals: u8 },
+    /// `0.25` as `25%`
+    Percent { decimals: u8 },
+    /// `1234` as `1.2k`, with SI prefixes up to `E`, followed by `unit`, e.g. `W`
+    Si { decimals: u8, unit: String },
+    /// `1536` as `1.5 KB`, in powers of 1024
+    Bytes { decimals: u8 },
+    /// Milliseconds, e.g. `3723000` as `1h 2m 3s`, or `250ms` under a second
+    Duration,
+    /// `3.14159` as `3.14`
+    Fixed { decimals: u8 },
+    /// `1234567` as `1,234,567`
+    Thousands { decimals: u8 },
+    /// A timestamp in milliseconds, formatted with the tokens of date-fns, e.g. `MMM d, yyyy`.
+    /// Uses the chart's date adapter in the browser
+    Date { pattern: String },
+}
+
+const SI_PREFIXES: [&str; 7] = ["", "k", "M", "G", "T", "P", "E"];
+const BYTE_UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
+const DURATION_UNITS: [(i64, &str); 4] = [
+    (24 * 60 * 60 * 1000, "d"),
+    (60 * 60 * 1000, "h"),
+    (60 * 1000, "m"),
+    (1000, "s"),
+];
+
+impl Formatter {
+    /// A tick `callback`, for [`ScaleTicks`](crate::ScaleTicks)
+    pub fn tick_callback(&self) -> FnWithArgs {
+        self.function(
+            &["value", "index", "ticks"],
+            "value",
+            "this.chart.options.locale",
+        )
+    }
+
+    /// A tooltip `label` callback, for [`TooltipCallbacks`](crate::TooltipCallbacks),
+    /// which shows the dataset's label and the formatted value
+    pub fn tooltip_label(&self) -> FnWithArgs {
+        let value = "typeof context.parsed === 'object' && context.parsed !== null \
+            ? (context.chart.options.indexAxis === 'y' ? context.parsed.x : context.parsed.y) \
+            : context.parsed";
+        let mut label = self.function(&["context"], value, "context.chart.options.locale");
+        label.body = format!(
+            "(context.dataset.label ? context.dataset.label + ': ' : '') + {}",
+            label.body
+        );
+        label
+    }
+
+    /// A data label `formatter`, for [`DataLabels`](crate::DataLabels)
+    pub fn datalabels_formatter(&self) -> FnWithArgs {
+        self.function(
+            &["value", "context"],
+            "typeof value === 'object' && value !== null ? value.y : value",
+            "context.chart.options.locale",
+        )
+    }
+
+    /// A function of `args`, formatting the JS expression `value`
+    fn function(&self, args: &[&str], value: &str, locale: &str) -> FnWithArgs {
+        FnWithArgs {
+            args: args.iter().map(|a| a.to_string()).collect(),
+            body: format!(
+                "((v, locale) => {})({value}, {locale})",
+                self.js_expression()
+            ),
+        }
+    }
+
+    /// A JS expression of `v`, the number to format, and `locale`
+    fn js_expression(&self) -> String {
+        let number_format =
+            |options: String| format!("new Intl.NumberFormat(locale, {{ {options} }}).format(v)");
+        let digits = |d: &u8| format!("minimumFractionDigits: {d}, maximumFractionDigits: {d}");
+        // values just under `base` can round up to it, and then go in the next unit
+        let scaled = |base: u32, units: &[&str], separator: &str, d: &u8| {
+            format!(
+                "(() => {{ const u = {}; let n = Math.abs(v), i = 0; \
+                while (n >= {base} && i < u.length - 1) {{ n /= {base}; i++; }} \
+                if (Number(n.toFixed({d})) >= {base} && i < u.length - 1) {{ n /= {base}; i++; }} \
+                return (v < 0 ? '-' : '') + Number(n.toFixed({d})) + {} + u[i]; }})()",
+                json(&units),
+                json(&separator),
+            )
+        };
+
+        let expression = match self {
+            Self::Currency { currency, decimals } => number_format(format!(
+                "style: 'currency', currency: {}, {}",
+                json(currency),
+                digits(decimals)
+            )),
+            Self::Percent { decimals } => {
+                number_format(format!("style: 'percent', {}", digits(decimals)))
+            }
+            Self::Si { decimals, unit } => {
+                let units = SI_PREFIXES
+                    .iter()
+                    .map(|prefix| format!("{prefix}{unit}"))
+                    .collect::<Vec<_>>();
+                scaled(
+                    1000,
+                    &units.iter().map(String::as_str).collect::<Vec<_>>(),
+                    "",
+                    decimals,
+                )
+            }
+            Self::Bytes { decimals } => scaled(1024, &BYTE_UNITS, " ", decimals),
+            Self::Duration => format!(
+                "(() => {{ let n = Math.abs(Math.round(v)); const sign = v < 0 ? '-' : ''; \
+                if (n < 1000) return sign + n + 'ms'; \
+                return sign + {}.map(([ms, u]) => {{ const q = Math.floor(n / ms); n -= q * ms; \
+                return q ? q + u : ''; }}).filter(p => p).join(' '); }})()",
+                json(&DURATION_UNITS)
+            ),
+            Self::Fixed { decimals } => format!("Number(v).toFixed({decimals})"),
+            Self::Thousands { decimals } => number_format(digits(decimals)),
+            Self::Date { pattern } => {
+                format!("new Chart._adapters._date().format(v, {})", json(pattern))
+            }
+        };
+
+        if self.uses_intl() {
+            expression
+        } else {
+            format!("(isFinite(v) ? {expression} : new Intl.NumberFormat(locale).format(v))")
+        }
+    }
+
+    /// Whether the formatter is `Intl.NumberFormat` in the browser, which shows `NaN` and
+    /// infinities as `NaN` and `∞`. The other formatters show them the same way
+    fn uses_intl(&self) -> bool {
+        matches!(
+            self,
+            Self::Currency { .. } | Self::Percent { .. } | Self::Thousands { .. }
+        )
+    }
+
+    /// Formats `value` in Rust, as the compiled callbacks would
+    pub fn format(&self, value: f64) -> String {
+        if !value.is_finite() && !self.uses_intl() {
+            return thousands(value, 0, 0);
+        }
+
+        let sign = if value < 0.0 { "-" } else { "" };
+        let scaled = |base: f64, units: &[String], separator: &str, d: usize| {
+            let (mut n, mut i) = (value.abs(), 0);
+            while n >= base && i < units.len() - 1 {
+                n /= base;
+                i += 1;
+            }
+            if to_fixed(n, d).parse::<f64>().unwrap() >= base && i < units.len() - 1 {
+                n /= base;
+                i += 1;
+            }
+            format!(
+                "{sign}{}{separator}{}",
+                trim_zeros(to_fixed(n, d)),
+                units[i]
+            )
+        };
+
+        match self {
+            Self::Currency { currency, decimals } => {
+                let amount = thousands(value.abs(), 0, *decimals as usize);
+                match currency_symbol(currency) {
+                    Some(symbol) => format!("{sign}{symbol}{amount}"),
+                    None if !value.is_finite() => format!("{sign}{currency}{amount}"),
+                    None => format!("{sign}{currency} {amount}"),
+                }
+            }
+            Self::Percent { decimals } => {
+                format!("{}%", thousands(value, 2, *decimals as usize))
+            }
+            Self::Si { decimals, unit } => scaled(
+                1000.0,
+                &SI_PREFIXES.map(|prefix| format!("{prefix}{unit}")),
+                "",
+                *decimals as usize,
+            ),
+            Self::Bytes { decimals } => scaled(
+                1024.0,
+                &BYTE_UNITS.map(String::from),
+                " ",
+                *decimals as usize,
+            ),
+            Self::Duration => {
+                let mut n = value.abs().round() as i64;
+                if n < 1000 {
+                    return format!("{sign}{n}ms");
+                }
+                let parts = DURATION_UNITS
+                    .iter()
+                    .filter_map(|(ms, unit)| {
+                        let q = n / ms;
+                        n -= q * ms;
+                        (q > 0).then(|| format!("{q}{unit}"))
+                    })
+                    .collect::<Vec<_>>();
+                format!("{sign}{}", parts.join(" "))
+            }
+            Self::Fixed { decimals } => format!("{sign}{}", to_fixed(value, *decimals as usize)),
+            Self::Thousands { decimals } => thousands(value, 0, *decimals as usize),
+            Self::Date { pattern } => DateParts::from_utc_millis(value as i64).format(pattern),
+        }
+    }
+}
+
+fn json(value: &impl serde::Serialize) -> String {
+    serde_json::to_string(value).expect("Unable to serialize formatter option.")
+}
+
+/// The magnitude of `value` with `decimals` decimal places, like `toFixed` in JS,
+/// which rounds halves away from zero where Rust rounds them to even
+fn to_fixed(value: f64, decimals: usize) -> String {
+    let scaled = value.abs() * 10f64.powi(decimals as i32);
+    if scaled.fract() != 0.5 || scaled >= 2f64.powi(53) {
+        return format!("{:.decimals$}", value.abs());
+    }
+
+    let digits = format!("{:0>width$}", scaled.ceil() as u64, width = decimals + 1);
+    let (int, frac) = digits.split_at(digits.len() - decimals);
+    match decimals {
+        0 => int.to_string(),
+        _ => format!("{int}.{frac}"),
+    }
+}
+
+/// The magnitude of `value` times 10^`shift` with `decimals` decimal places, like
+/// `Intl.NumberFormat`, which rounds the shortest decimal that reads back as `value`,
+/// e.g. `1.005`, rather than the exact binary one, which is just under it
+fn intl_fixed(value: f64, shift: usize, decimals: usize) -> String {
+    let repr = format!("{}", value.abs());
+    let (int, frac) = repr.split_once('.').unwrap_or((&repr, ""));
+    let mut digits = format!("{int}{frac}").into_bytes();
+    let point = int.len() + shift;
+    digits.resize(digits.len().max(point + decimals + 1), b'0');
+
+    let round_up = digits[point + decimals] >= b'5';
+    digits.truncate(point + decimals);
+    if round_up {
+        match digits.iter().rposition(|&d| d != b'9') {
+            Some(i) => {
+                digits[i] += 1;
+                digits[i + 1..].fill(b'0');
+            }
+            None => {
+                digits.fill(b'0');
+                digits.insert(0, b'1');
+            }
+        }
+    }
+
+    let digits = String::from_utf8(digits).unwrap();
+    let (int, frac) = digits.split_at(digits.len() - decimals);
+    let int = match int.trim_start_matches('0') {
+        "" => "0",
+        int => int,
+    };
+    match decimals {
+        0 => int.to_string(),
+        _ => format!("{int}.{frac}"),
+    }
+}
+
+/// `value` formatted like `Intl.NumberFormat` with `decimals` decimal places, and `,` between thousands.
+/// `NaN` and infinities are `NaN` and `∞`
+fn thousands(value: f64, shift: usize, decimals: usize) -> String {
+    let sign = if value < 0.0 { "-" } else { "" };
+    if value.is_nan() {
+        return "NaN".to_string();
+    } else if value.is_infinite() {
+        return format!("{sign}∞");
+    }
+
+    let fixed = intl_fixed(value, shift, decimals);
+    let (int, frac) = fixed.split_at(fixed.find('.').unwrap_or(fixed.len()));
+    let grouped = int
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .map(|chunk| std::str::from_utf8(chunk).unwrap())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("{sign}{grouped}{frac}")
+}
+
+/// Drops the trailing zeros of a decimal, like `Number(n.toFixed(d))` in JS
+fn trim_zeros(fixed: String) -> String {
+    if fixed.contains('.') {
+        fixed
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        fixed
+    }
+}
+
+fn currency_symbol(currency: &str) -> Option<&'static str> {
+    match currency {
+        "USD" => Some("$"),
+        "EUR" => Some("€"),
+        "GBP" => Some("£"),
+        "JPY" | "CNY" => Some("¥"),
+        "INR" => Some("₹"),
+        "KRW" => Some("₩"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn si(decimals: u8) -> Formatter {
+        Formatter::Si {
+            decimals,
+            unit: "W".into(),
+        }
+    }
+
+    fn usd() -> Formatter {
+        Formatter::Currency {
+            currency: "USD".into(),
+            decimals: 2,
+        }
+    }
+
+    #[test]
+    fn currency() {
+        assert_eq!(usd().format(1234.5), "$1,234.50");
+        assert_eq!(usd().format(-0.5), "-$0.50");
+        let chf = Formatter::Currency {
+            currency: "CHF".into(),
+            decimals: 0,
+        };
+        assert_eq!(chf.format(1234567.0), "CHF 1,234,567");
+    }
+
+    #[test]
+    fn rounding_ties() {
+        // `toFixed` rounds the exact binary value, which is just under 1.005
+        assert_eq!(Formatter::Fixed { decimals: 2 }.format(1.005), "1.00");
+        assert_eq!(Formatter::Fixed { decimals: 1 }.format(0.25), "0.3");
+        assert_eq!(Formatter::Fixed { decimals: 0 }.format(-2.5), "-3");
+        // `Intl.NumberFormat` rounds the shortest decimal, `1.005`
+        assert_eq!(Formatter::Thousands { decimals: 2 }.format(1.005), "1.01");
+        assert_eq!(Formatter::Percent { decimals: 1 }.format(0.12345), "12.3%");
+        assert_eq!(Formatter::Thousands { decimals: 0 }.format(999.5), "1,000");
+    }
+
+    #[test]
+    fn scaled_units() {
+        assert_eq!(si(1).format(0.0), "0W");
+        assert_eq!(si(1).format(1234.0), "1.2kW");
+        assert_eq!(si(1).format(-1500.0), "-1.5kW");
+        assert_eq!(Formatter::Bytes { decimals: 1 }.format(1536.0), "1.5 KB");
+        assert_eq!(Formatter::Bytes { decimals: 1 }.format(999.96), "1000 B");
+    }
+
+    #[test]
+    fn scaled_units_roll_over_after_rounding() {
+        assert_eq!(si(1).format(999.96), "1kW");
+        assert_eq!(si(1).format(999_999.0), "1MW");
+        assert_eq!(si(1).format(-999.96), "-1kW");
+        assert_eq!(si(1).format(999.94), "999.9W");
+        assert_eq!(Formatter::Bytes { decimals: 1 }.format(1023.96), "1 KB");
+        // there is no unit after the last one
+        assert_eq!(si(0).format(999.6e18), "1000EW");
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(Formatter::Duration.format(250.0), "250ms");
+        assert_eq!(Formatter::Duration.format(3_723_000.0), "1h 2m 3s");
+        assert_eq!(Formatter::Duration.format(-86_400_000.0), "-1d");
+    }
+
+    #[test]
+    fn non_finite() {
+        let percent = Formatter::Percent { decimals: 0 };
+        let thousands = Formatter::Thousands { decimals: 2 };
+        let date = Formatter::Date {
+            pattern: "MMM d, yyyy".into(),
+        };
+
+        assert_eq!(usd().format(f64::NAN), "$NaN");
+        assert_eq!(usd().format(f64::NEG_INFINITY), "-$∞");
+        assert_eq!(percent.format(f64::NAN), "NaN%");
+        assert_eq!(percent.format(f64::INFINITY), "∞%");
+        assert_eq!(thousands.format(f64::NEG_INFINITY), "-∞");
+        assert_eq!(si(1).format(f64::INFINITY), "∞");
+        assert_eq!(Formatter::Duration.format(f64::NAN), "NaN");
+        assert_eq!(date.format(f64::NAN), "NaN");
+        assert_eq!(date.format(0.0), "Jan 1, 1970");
+    }
+
+    #[test]
+    fn callbacks_guard_non_finite_values() {
+        assert!(si(1).tick_callback().body.contains("isFinite(v) ? "));
+        assert!(!usd().tick_callback().body.contains("isFinite"));
+    }
+}
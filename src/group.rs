@@ -0,0 +1,264 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+use crate::{types::*, utils::js_error, ChartError, ChartHandle, Color};
+
+/// Links several rendered charts that share an x scale, so that hovering one
+/// draws a crosshair and shows tooltips at the same x value on all of them,
+/// and zooming one (e.g. with `chartjs-plugin-zoom`) zooms the others.
+///
+/// ```rust,ignore
+///   ChartGroup::new(&["prices", "volume", "volatility"]).link()?;
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ChartGroup {
+    /// The ids of the canvases of the charts in the group
+    pub ids: Vec<String>,
+
+    /// The id of the shared scale, `"x"` by default
+    pub axis: String,
+
+    /// How the hovered elements of the other charts are found
+    pub mode: InteractionMode,
+
+    pub crosshair: bool,
+
+    pub crosshairColor: Color,
+
+    pub crosshairWidth: NumberString,
+
+    pub syncTooltips: bool,
+
+    pub syncZoom: bool,
+}
+
+impl Default for ChartGroup {
+    fn default() -> Self {
+        Self {
+            ids: Vec::new(),
+            axis: "x".into(),
+            mode: InteractionMode::Nearest,
+            crosshair: true,
+            crosshairColor: "rgba(0, 0, 0, 0.3)".into(),
+            crosshairWidth: 1.into(),
+            syncTooltips: true,
+            syncZoom: true,
+        }
+    }
+}
+
+impl ChartGroup {
+    pub fn new(ids: &[&str]) -> Self {
+        Self {
+            ids: ids.iter().map(|id| id.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn handles(&self) -> Vec<ChartHandle> {
+        self.ids.iter().map(|id| ChartHandle::new(id)).collect()
+    }
+
+    /// Starts synchronising the charts, they must all be rendered first.
+    /// Linking a chart again replaces the group it was previously in
+    pub fn link(&self) -> Result<(), ChartError> {
+        let group = serde_wasm_bindgen::to_value(self)?;
+        link_charts(group).map_err(|e| self.error(e))
+    }
+
+    pub fn unlink(&self) -> Result<(), ChartError> {
+        let ids = serde_wasm_bindgen::to_value(&self.ids)?;
+        unlink_charts(ids).map_err(|e| self.error(e))
+    }
+
+    /// Sets the range of the shared scale on every chart in the group
+    pub fn zoom(
+        &self,
+        min: impl Into<NumberOrDateString>,
+        max: impl Into<NumberOrDateString>,
+    ) -> Result<(), ChartError> {
+        let ids = serde_wasm_bindgen::to_value(&self.ids)?;
+        let min = serde_wasm_bindgen::to_value(&min.into())?;
+        let max = serde_wasm_bindgen::to_value(&max.into())?;
+        zoom_charts(ids, &self.axis, min, max).map_err(|e| self.error(e))
+    }
+
+    pub fn reset_zoom(&self) -> Result<(), ChartError> {
+        let ids = serde_wasm_bindgen::to_value(&self.ids)?;
+        zoom_charts(ids, &self.axis, JsValue::UNDEFINED, JsValue::UNDEFINED)
+            .map_err(|e| self.error(e))
+    }
+
+    fn error(&self, e: JsValue) -> ChartError {
+        match e.as_string() {
+            Some(id) if self.ids.contains(&id) => ChartError::ChartNotFound(id),
+            _ => js_error(e),
+        }
+    }
+}
+
+#[wasm_bindgen(inline_js = r#"function get_charts(ids) {
+    return ids.map(id => {
+        const chart = Chart.getChart(document.getElementById(id));
+        if (!chart) {
+            throw id;
+        }
+
+        return chart;
+    });
+}
+
+function sync(source, group, value) {
+    get_charts(group.ids).forEach(chart => {
+        const scale = chart.scales[group.axis];
+        if (!scale) {
+            return;
+        }
+
+        const pixel = value === null ? null : scale.getPixelForValue(value);
+        chart.$chartJsRsCrosshair = pixel;
+
+        if (chart !== source && group.syncTooltips) {
+            if (pixel === null) {
+                chart.setActiveElements([]);
+                chart.tooltip.setActiveElements([], { x: 0, y: 0 });
+            } else {
+                const position = { x: pixel, y: (chart.chartArea.top + chart.chartArea.bottom) / 2 };
+                const elements = chart
+                    .getElementsAtEventForMode(
+                        { type: 'mousemove', native: null, x: position.x, y: position.y },
+                        group.mode,
+                        { intersect: false, axis: 'x' },
+                        false,
+                    )
+                    .map(e => ({ datasetIndex: e.datasetIndex, index: e.index }));
+
+                chart.setActiveElements(elements);
+                chart.tooltip.setActiveElements(elements, position);
+            }
+        }
+
+        if (chart !== source) {
+            chart.render();
+        }
+    });
+}
+
+const plugin = {
+    id: 'chartJsRsGroup',
+    afterEvent(chart, args) {
+        const group = chart.$chartJsRsGroup;
+        const scale = group && chart.scales[group.axis];
+        if (!scale) {
+            return;
+        }
+
+        const event = args.event;
+        if (event.type === 'mouseout' || !args.inChartArea) {
+            sync(chart, group, null);
+        } else if (event.type === 'mousemove') {
+            sync(chart, group, scale.getValueForPixel(event.x));
+        }
+
+        args.changed = true;
+    },
+    afterDatasetsDraw(chart) {
+        const group = chart.$chartJsRsGroup;
+        const x = chart.$chartJsRsCrosshair;
+        if (!group || !group.crosshair || x === null || x === undefined) {
+            return;
+        }
+
+        const { ctx, chartArea } = chart;
+        if (x < chartArea.left || x > chartArea.right) {
+            return;
+        }
+
+        ctx.save();
+        ctx.beginPath();
+        ctx.strokeStyle = group.crosshairColor;
+        ctx.lineWidth = group.crosshairWidth;
+        ctx.moveTo(x, chartArea.top);
+        ctx.lineTo(x, chartArea.bottom);
+        ctx.stroke();
+        ctx.restore();
+    },
+    afterUpdate(chart) {
+        const group = chart.$chartJsRsGroup;
+        const options = group && chart.options.scales[group.axis];
+        if (!options || !group.syncZoom || group.$syncing) {
+            return;
+        }
+
+        const { min, max } = options;
+        if (group.$range.min === min && group.$range.max === max) {
+            return;
+        }
+
+        group.$range = { min, max };
+        group.$syncing = true;
+        try {
+            get_charts(group.ids)
+                .filter(c => c !== chart && c.options.scales[group.axis])
+                .forEach(c => {
+                    c.options.scales[group.axis].min = min;
+                    c.options.scales[group.axis].max = max;
+                    c.update('none');
+                });
+        } finally {
+            group.$syncing = false;
+        }
+    },
+};
+
+export function link_charts(group) {
+    const charts = get_charts(group.ids);
+
+    const options = charts.length > 0 && charts[0].options.scales[group.axis];
+    group.$range = options ? { min: options.min, max: options.max } : {};
+
+    charts.forEach(chart => {
+        chart.$chartJsRsGroup = group;
+        chart.$chartJsRsCrosshair = null;
+
+        if (!chart.config.plugins.includes(plugin)) {
+            chart.config.plugins.push(plugin);
+            chart.update('none');
+        }
+    });
+}
+
+export function unlink_charts(ids) {
+    get_charts(ids).forEach(chart => {
+        delete chart.$chartJsRsGroup;
+        delete chart.$chartJsRsCrosshair;
+        chart.render();
+    });
+}
+
+export function zoom_charts(ids, axis, min, max) {
+    get_charts(ids).forEach(chart => {
+        const options = chart.options.scales[axis];
+        if (!options) {
+            return;
+        }
+
+        options.min = min;
+        options.max = max;
+        if (chart.$chartJsRsGroup) {
+            chart.$chartJsRsGroup.$range = { min, max };
+        }
+
+        chart.update('none');
+    });
+}"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn link_charts(group: JsValue) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch)]
+    fn unlink_charts(ids: JsValue) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(catch)]
+    fn zoom_charts(ids: JsValue, axis: &str, min: JsValue, max: JsValue) -> Result<(), JsValue>;
+}
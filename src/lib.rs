@@ -1,19 +1,41 @@
 #![allow(non_snake_case)]
 #![doc = include_str!("../README.md")]
 
+pub mod axes;
 pub mod bar;
+pub mod builder;
+pub mod color;
+#[cfg(feature = "date-adapter")]
+pub mod date_adapter;
+pub mod dates;
+pub mod defaults;
 pub mod doughnut;
+pub mod error;
+pub mod formatters;
 pub mod functions;
+pub mod group;
+pub mod macros;
+pub mod merge;
+pub mod palette;
 pub mod pie;
 pub mod scatter;
+pub mod theme;
 pub mod types;
 
 #[doc(hidden)]
 pub mod utils;
+pub mod validate;
 
+#[cfg(feature = "derive")]
+pub use chart_js_rs_derive::ChartData;
+pub use color::{Color, Paint};
+pub use dates::ChartDate;
+pub use error::ChartError;
+pub use formatters::Formatter;
 use gloo_utils::format::JsValueSerdeExt;
 use serde::{de::DeserializeOwned, Serialize};
 pub use types::*;
+pub use utils::ChartHandle;
 
 use utils::Chart;
 use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
@@ -38,6 +60,17 @@ pub trait ChartExt: DeserializeOwned + Serialize {
             })
             .ok()
     }
+
+    /// Checks the chart for problems Chart.js doesn't report, without a browser,
+    /// see [`validate::validate`]
+    fn validate(&self) -> Vec<validate::Diagnostic> {
+        validate::validate(&serde_json::to_value(self).expect("Unable to serialize chart."))
+    }
+
+    /// Returns an error if a dataset is drawn on a scale that isn't configured, see [`axes::check_axes`]
+    fn check_axes(&self) -> Result<(), ChartError> {
+        axes::check_axes(self)
+    }
 }
 
 #[wasm_bindgen(inline_js = r#"export function render_chart(v, id, mutate) {
@@ -58,6 +91,31 @@ pub trait ChartExt: DeserializeOwned + Serialize {
 }"#)]
 extern "C" {
     fn render_chart(v: JsValue, id: &str, mutate: bool);
+
+    #[wasm_bindgen(catch, js_name = render_chart)]
+    fn try_render_chart(v: JsValue, id: &str, mutate: bool) -> Result<(), JsValue>;
+}
+
+#[wasm_bindgen(inline_js = r#"export function needs_date_adapter(v) {
+    const scales = (v.options && v.options.scales) || {};
+    const time = Object.values(scales)
+        .some(scale => scale && (scale.type === 'time' || scale.type === 'timeseries'));
+    // without Chart.js, rendering throws a clearer error
+    if (!time || typeof Chart === 'undefined') {
+        return false;
+    }
+
+    // the placeholder adapter throws from every method
+    try {
+        new Chart._adapters._date().formats();
+        return false;
+    }
+    catch {
+        return true;
+    }
+}"#)]
+extern "C" {
+    fn needs_date_adapter(v: &JsValue) -> bool;
 }
 
 #[wasm_bindgen(inline_js = r#"export function get_chart(id) {
@@ -70,10 +128,20 @@ extern "C" {
 #[wasm_bindgen(inline_js = r#"export function update_chart(updated, id, animate) {
     try {
         let chart = Chart.getChart(document.getElementById(id));
+
+        // replacing the datasets drops their metadata, so carry visibility over by index
+        const hidden = chart.data.datasets.map((_, i) => chart.getDatasetMeta(i).hidden);
+
         chart.config._config.type = updated.type;
         chart.config._config.data = updated.data;
         chart.config._config.options = updated.options;
 
+        hidden.forEach((h, i) => {
+            if (i < chart.data.datasets.length) {
+                chart.getDatasetMeta(i).hidden = h;
+            }
+        });
+
         console.debug('Updated chart:', chart);
 
         if (animate) {
@@ -91,3 +159,363 @@ extern "C" {
 extern "C" {
     fn update_chart(updated: JsValue, id: &str, animate: bool) -> bool;
 }
+
+#[wasm_bindgen(
+    inline_js = r#"export function elements_at(id, x, y, mode, intersect) {
+    const chart = Chart.getChart(document.getElementById(id));
+    if (!chart) {
+        throw 'ChartNotFound';
+    }
+
+    const event = { type: 'mousemove', native: null, x: x, y: y };
+    return chart
+        .getElementsAtEventForMode(event, mode, { intersect: intersect }, false)
+        .map(e => ({
+            datasetIndex: e.datasetIndex,
+            index: e.index,
+            value: chart.data.datasets[e.datasetIndex].data[e.index],
+        }));
+}"#
+)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn elements_at(
+        id: &str,
+        x: f64,
+        y: f64,
+        mode: JsValue,
+        intersect: bool,
+    ) -> Result<JsValue, JsValue>;
+}
+
+#[wasm_bindgen(
+    inline_js = r#"export function set_dataset_visibility(id, datasetIndex, visible) {
+    const chart = Chart.getChart(document.getElementById(id));
+    if (!chart) {
+        throw 'ChartNotFound';
+    }
+
+    chart.setDatasetVisibility(datasetIndex, visible);
+    chart.update();
+}"#
+)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn set_dataset_visibility(id: &str, datasetIndex: usize, visible: bool) -> Result<(), JsValue>;
+}
+
+#[wasm_bindgen(inline_js = r#"export function hide(id, datasetIndex, dataIndex) {
+    const chart = Chart.getChart(document.getElementById(id));
+    if (!chart) {
+        throw 'ChartNotFound';
+    }
+
+    chart.hide(datasetIndex, dataIndex);
+}"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn hide(id: &str, datasetIndex: usize, dataIndex: Option<usize>) -> Result<(), JsValue>;
+}
+
+#[wasm_bindgen(inline_js = r#"export function show(id, datasetIndex, dataIndex) {
+    const chart = Chart.getChart(document.getElementById(id));
+    if (!chart) {
+        throw 'ChartNotFound';
+    }
+
+    chart.show(datasetIndex, dataIndex);
+}"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn show(id: &str, datasetIndex: usize, dataIndex: Option<usize>) -> Result<(), JsValue>;
+}
+
+#[wasm_bindgen(inline_js = r#"export function toggle_data_visibility(id, index) {
+    const chart = Chart.getChart(document.getElementById(id));
+    if (!chart) {
+        throw 'ChartNotFound';
+    }
+
+    chart.toggleDataVisibility(index);
+    chart.update();
+}"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn toggle_data_visibility(id: &str, index: usize) -> Result<(), JsValue>;
+}
+
+#[wasm_bindgen(inline_js = r#"export function is_dataset_visible(id, datasetIndex) {
+    const chart = Chart.getChart(document.getElementById(id));
+    if (!chart) {
+        throw 'ChartNotFound';
+    }
+
+    return chart.isDatasetVisible(datasetIndex);
+}"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn is_dataset_visible(id: &str, datasetIndex: usize) -> Result<bool, JsValue>;
+}
+
+#[wasm_bindgen(inline_js = r#"export function get_data_visibility(id, index) {
+    const chart = Chart.getChart(document.getElementById(id));
+    if (!chart) {
+        throw 'ChartNotFound';
+    }
+
+    return chart.getDataVisibility(index);
+}"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn get_data_visibility(id: &str, index: usize) -> Result<bool, JsValue>;
+}
+
+#[wasm_bindgen(inline_js = r#"export function set_active_elements(id, elements) {
+    const chart = Chart.getChart(document.getElementById(id));
+    if (!chart) {
+        throw 'ChartNotFound';
+    }
+
+    chart.setActiveElements(elements);
+    chart.update();
+}"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn set_active_elements(id: &str, elements: JsValue) -> Result<(), JsValue>;
+}
+
+#[wasm_bindgen(inline_js = r#"export function get_active_elements(id) {
+    const chart = Chart.getChart(document.getElementById(id));
+    if (!chart) {
+        throw 'ChartNotFound';
+    }
+
+    return chart.getActiveElements().map(e => ({
+        datasetIndex: e.datasetIndex,
+        index: e.index,
+        value: chart.data.datasets[e.datasetIndex].data[e.index],
+    }));
+}"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn get_active_elements(id: &str) -> Result<JsValue, JsValue>;
+}
+
+#[wasm_bindgen(
+    inline_js = r#"export function set_tooltip_active_elements(id, elements, x, y) {
+    const chart = Chart.getChart(document.getElementById(id));
+    if (!chart) {
+        throw 'ChartNotFound';
+    }
+
+    let position = { x: x, y: y };
+    if ((x === undefined || y === undefined) && elements.length > 0) {
+        // default to the position of the first element, as if it were being hovered
+        const element = chart.getDatasetMeta(elements[0].datasetIndex).data[elements[0].index];
+        position = element ? { x: element.x, y: element.y } : { x: 0, y: 0 };
+    }
+
+    chart.tooltip.setActiveElements(elements, position);
+    chart.update();
+}"#
+)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn set_tooltip_active_elements(
+        id: &str,
+        elements: JsValue,
+        x: Option<f64>,
+        y: Option<f64>,
+    ) -> Result<(), JsValue>;
+}
+
+#[wasm_bindgen(inline_js = r#"function image_canvas(id, background) {
+    const chart = Chart.getChart(document.getElementById(id));
+    if (!chart) {
+        throw 'ChartNotFound';
+    }
+
+    if (!background) {
+        return chart.canvas;
+    }
+
+    // chart canvases are transparent, so draw onto a filled copy
+    const canvas = document.createElement('canvas');
+    canvas.width = chart.canvas.width;
+    canvas.height = chart.canvas.height;
+
+    const ctx = canvas.getContext('2d');
+    ctx.fillStyle = background;
+    ctx.fillRect(0, 0, canvas.width, canvas.height);
+    ctx.drawImage(chart.canvas, 0, 0);
+
+    return canvas;
+}
+
+export function to_base64_image(id, mime, quality, background) {
+    return image_canvas(id, background).toDataURL(mime, quality);
+}
+
+export function to_blob(id, mime, quality, background) {
+    const canvas = image_canvas(id, background);
+    return new Promise((resolve, reject) => canvas.toBlob(
+        blob => blob ? resolve(blob) : reject('Unable to create an image from the chart'),
+        mime,
+        quality,
+    ));
+}
+
+export function download_image(id, fileName, mime, quality, background) {
+    const link = document.createElement('a');
+    link.href = image_canvas(id, background).toDataURL(mime, quality);
+    link.download = fileName;
+    link.click();
+}"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn to_base64_image(
+        id: &str,
+        mime: &str,
+        quality: Option<f64>,
+        background: Option<String>,
+    ) -> Result<String, JsValue>;
+
+    #[wasm_bindgen(catch)]
+    fn to_blob(
+        id: &str,
+        mime: &str,
+        quality: Option<f64>,
+        background: Option<String>,
+    ) -> Result<js_sys::Promise, JsValue>;
+
+    #[wasm_bindgen(catch)]
+    fn download_image(
+        id: &str,
+        fileName: &str,
+        mime: &str,
+        quality: Option<f64>,
+        background: Option<String>,
+    ) -> Result<(), JsValue>;
+}
+
+#[wasm_bindgen(inline_js = r#"function gradient(chart, spec) {
+    const { ctx, chartArea: area } = chart;
+    const scale = spec.scale ? chart.scales[spec.scale] : undefined;
+
+    let gradient;
+    let offset = stop => stop.offset;
+    if (spec.type === 'radial') {
+        const radial = scale && scale.xCenter !== undefined;
+        const x = radial ? scale.xCenter : (area.left + area.right) / 2;
+        const y = radial ? scale.yCenter : (area.top + area.bottom) / 2;
+        const r = radial ? scale.drawingArea : Math.hypot(area.width, area.height) / 2;
+
+        gradient = ctx.createRadialGradient(x, y, 0, x, y, r);
+        if (radial) {
+            offset = stop => scale.getDistanceFromCenterForValue(stop.offset) / r;
+        }
+    } else if (scale) {
+        const [start, end] = scale.isHorizontal() ? [area.left, area.right] : [area.bottom, area.top];
+
+        gradient = scale.isHorizontal()
+            ? ctx.createLinearGradient(start, 0, end, 0)
+            : ctx.createLinearGradient(0, start, 0, end);
+        offset = stop => (scale.getPixelForValue(stop.offset) - start) / (end - start);
+    } else {
+        const [x0, y0, x1, y1] = {
+            toTop: [0, area.bottom, 0, area.top],
+            toBottom: [0, area.top, 0, area.bottom],
+            toLeft: [area.right, 0, area.left, 0],
+            toRight: [area.left, 0, area.right, 0],
+        }[spec.direction];
+
+        gradient = ctx.createLinearGradient(x0, y0, x1, y1);
+    }
+
+    spec.stops.forEach(stop => {
+        gradient.addColorStop(Math.min(Math.max(offset(stop), 0), 1), stop.color);
+    });
+    return gradient;
+}
+
+function pattern(chart, spec) {
+    const size = spec.size || 10;
+    const canvas = document.createElement('canvas');
+    canvas.width = size;
+    canvas.height = size;
+
+    const ctx = canvas.getContext('2d');
+    if (spec.background) {
+        ctx.fillStyle = spec.background;
+        ctx.fillRect(0, 0, size, size);
+    }
+
+    ctx.fillStyle = spec.color;
+    ctx.strokeStyle = spec.color;
+    ctx.lineWidth = spec.lineWidth || 1;
+    ctx.beginPath();
+
+    const diagonal = (flip) => {
+        // the extra lines through the corners keep the tiles seamless
+        [-size, 0, size].forEach(o => {
+            ctx.moveTo(o, flip ? 0 : size);
+            ctx.lineTo(o + size, flip ? size : 0);
+        });
+    };
+    switch (spec.shape) {
+        case 'diagonal':
+            diagonal(false);
+            break;
+        case 'reverseDiagonal':
+            diagonal(true);
+            break;
+        case 'horizontal':
+            ctx.moveTo(0, size / 2);
+            ctx.lineTo(size, size / 2);
+            break;
+        case 'vertical':
+            ctx.moveTo(size / 2, 0);
+            ctx.lineTo(size / 2, size);
+            break;
+        case 'cross':
+            ctx.moveTo(0, size / 2);
+            ctx.lineTo(size, size / 2);
+            ctx.moveTo(size / 2, 0);
+            ctx.lineTo(size / 2, size);
+            break;
+        case 'dots':
+            ctx.arc(size / 2, size / 2, ctx.lineWidth, 0, 2 * Math.PI);
+            ctx.fill();
+            break;
+    }
+    ctx.stroke();
+
+    return chart.ctx.createPattern(canvas, 'repeat');
+}
+
+export function paint(spec) {
+    let cache = { key: undefined, value: undefined };
+
+    return function (context) {
+        const chart = context.chart;
+        const area = chart.chartArea;
+        if (!area) {
+            // the chart area isn't known before the first layout
+            return undefined;
+        }
+
+        const scale = spec.gradient && spec.gradient.scale && chart.scales[spec.gradient.scale];
+        const key = [area.left, area.top, area.right, area.bottom, scale && scale.min, scale && scale.max].join();
+        if (cache.key !== key) {
+            cache = {
+                key: key,
+                value: spec.gradient ? gradient(chart, spec.gradient) : pattern(chart, spec.pattern),
+            };
+        }
+
+        return cache.value;
+    };
+}"#)]
+extern "C" {
+    fn paint(spec: JsValue) -> JsValue;
+}
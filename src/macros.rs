@@ -0,0 +1,360 @@
+use std::{collections::HashMap, hash::Hash};
+
+use serde::Serialize;
+
+use crate::{types::*, utils::FnWithArgs};
+
+/// Builds a chart, or any of its parts, from JSON-like syntax, so configs from the
+/// Chart.js docs can be pasted in with few changes. Field names are checked against the
+/// structs at compile time, and values are converted with `.into()`.
+///
+/// - `field: value` sets a field, `type` is accepted for `r#type`
+/// - `field: { .. }` fills in a nested struct or map, creating it if it's `None`
+/// - `"key": value` sets an entry of a map
+/// - `"key": { .. }` fills in an entry of a map. For a scale, that sets the options every type
+///   of scale has, like `display`, `grid` and `position`. Others, like `type`, `ticks` or
+///   `beginAtZero`, need the struct of the type of scale, e.g. `"y": LinearScale { .. }`
+/// - `field: [ .. ]` sets a list, whose items can also be `{ .. }`
+/// - `Type { .. }` builds a value of another type with the same syntax, e.g. a point in a dataset's `data`
+///
+/// ```
+///   use chart_js_rs::{bar::Bar, chart, types::*};
+///
+///   let chart = chart!(Bar<NoAnnotations> {
+///       id: "bar",
+///       data: {
+///           labels: ["Mon", "Tue", "Wed"],
+///           datasets: [{
+///               label: "Visitors",
+///               data: [4, 8, 15],
+///               backgroundColor: "rgba(54, 162, 235, 0.5)",
+///           }],
+///       },
+///       options: {
+///           plugins: { title: { display: true, text: "Visitors" } },
+///           scales: {
+///               "x": { grid: { display: false } },
+///               "y": LinearScale { beginAtZero: true },
+///           },
+///       },
+///   });
+/// ```
+#[macro_export]
+macro_rules! chart {
+    (@object $p:ident;) => {};
+    (@object $p:ident; type : $($rest:tt)*) => {
+        $crate::chart!(@object $p; r#type : $($rest)*)
+    };
+    (@object $p:ident; $f:ident : { $($v:tt)* } $(, $($rest:tt)*)?) => {
+        {
+            let __n = $crate::macros::DslNode::node(&mut $p.$f);
+            $crate::chart!(@object __n; $($v)*);
+        }
+        $crate::chart!(@object $p; $($($rest)*)?);
+    };
+    (@object $p:ident; $f:ident : [ $($v:tt)* ] $(, $($rest:tt)*)?) => {
+        {
+            $p.$f = Default::default();
+            let __l = &mut $p.$f;
+            $crate::chart!(@list __l; $($v)*);
+        }
+        $crate::chart!(@object $p; $($($rest)*)?);
+    };
+    (@object $p:ident; $f:ident : $($t:ident)::+ { $($v:tt)* } $(, $($rest:tt)*)?) => {
+        $p.$f = $crate::chart!(@typed $($t)::+ { $($v)* }).into();
+        $crate::chart!(@object $p; $($($rest)*)?);
+    };
+    (@object $p:ident; $f:ident : $e:expr $(, $($rest:tt)*)?) => {
+        $p.$f = ($e).into();
+        $crate::chart!(@object $p; $($($rest)*)?);
+    };
+    (@object $p:ident; $k:literal : { $($v:tt)* } $(, $($rest:tt)*)?) => {
+        {
+            #[allow(unused_mut)]
+            let mut __e = $crate::macros::DslEntry($p.entry($k.into()).or_default());
+            let __n = {
+                use $crate::macros::DslEntryNode as _;
+                __e.dsl_node()
+            };
+            $crate::chart!(@object __n; $($v)*);
+        }
+        $crate::chart!(@object $p; $($($rest)*)?);
+    };
+    (@object $p:ident; $k:literal : $($t:ident)::+ { $($v:tt)* } $(, $($rest:tt)*)?) => {
+        $p.insert($k.into(), $crate::chart!(@typed $($t)::+ { $($v)* }).into());
+        $crate::chart!(@object $p; $($($rest)*)?);
+    };
+    (@object $p:ident; $k:literal : $e:expr $(, $($rest:tt)*)?) => {
+        $p.insert($k.into(), ($e).into());
+        $crate::chart!(@object $p; $($($rest)*)?);
+    };
+
+    (@list $l:ident;) => {};
+    (@list $l:ident; { $($v:tt)* } $(, $($rest:tt)*)?) => {
+        {
+            let __v = $crate::macros::DslNode::node(&mut *$l);
+            __v.push(Default::default());
+            let __n = __v.last_mut().unwrap();
+            $crate::chart!(@object __n; $($v)*);
+        }
+        $crate::chart!(@list $l; $($($rest)*)?);
+    };
+    (@list $l:ident; $($t:ident)::+ { $($v:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::macros::DslPush::dsl_push(&mut *$l, $crate::chart!(@typed $($t)::+ { $($v)* }));
+        $crate::chart!(@list $l; $($($rest)*)?);
+    };
+    (@list $l:ident; $e:expr $(, $($rest:tt)*)?) => {
+        $crate::macros::DslPush::dsl_push(&mut *$l, $e);
+        $crate::chart!(@list $l; $($($rest)*)?);
+    };
+
+    (@typed $($t:ident)::+ { $($v:tt)* }) => {{
+        let mut __t = $($t)::+::default();
+        {
+            let __n = &mut __t;
+            $crate::chart!(@object __n; $($v)*);
+        }
+        __t
+    }};
+
+    ($t:ty { $($v:tt)* }) => {{
+        let mut __c: $t = Default::default();
+        {
+            let __n = &mut __c;
+            $crate::chart!(@object __n; $($v)*);
+        }
+        __c
+    }};
+}
+
+/// The struct, map or list that the fields of a `{ .. }` in [`chart!`] are set on
+#[doc(hidden)]
+pub trait DslNode {
+    type Inner;
+
+    fn node(&mut self) -> &mut Self::Inner;
+}
+
+impl<T: Default> DslNode for Option<T> {
+    type Inner = T;
+
+    fn node(&mut self) -> &mut T {
+        self.get_or_insert_with(Default::default)
+    }
+}
+
+impl<T> DslNode for Vec<T> {
+    type Inner = Self;
+
+    fn node(&mut self) -> &mut Self {
+        self
+    }
+}
+
+impl<K: Eq + Hash, V> DslNode for HashMap<K, V> {
+    type Inner = Self;
+
+    fn node(&mut self) -> &mut Self {
+        self
+    }
+}
+
+macro_rules! impl_dsl_node {
+    ($(impl$(<$g:ident: $b:ident>)? for $t:ty;)*) => {
+        $(
+            impl$(<$g: $b>)? DslNode for $t {
+                type Inner = Self;
+
+                fn node(&mut self) -> &mut Self {
+                    self
+                }
+            }
+        )*
+    };
+}
+impl_dsl_node! {
+    impl for FnWithArgs;
+    impl for ScaleCommon;
+    impl for ScaleTicks;
+    impl<A: Annotation> for ChartOptions<A>;
+    impl<D: DatasetTrait> for Dataset<D>;
+}
+
+/// A map entry that the fields of a `"key": { .. }` in [`chart!`] are set on
+#[doc(hidden)]
+pub struct DslEntry<'a, V>(pub &'a mut V);
+
+/// What the fields of a map entry are set on: the entry itself, or for scales,
+/// the options every type of scale has
+#[doc(hidden)]
+pub trait DslEntryNode {
+    type Node;
+
+    fn dsl_node(self) -> Self::Node;
+}
+
+impl<'a> DslEntryNode for DslEntry<'a, ChartScale> {
+    type Node = &'a mut ScaleCommon;
+
+    fn dsl_node(self) -> &'a mut ScaleCommon {
+        self.0.common_mut()
+    }
+}
+
+impl<'a, V> DslEntryNode for &'a mut DslEntry<'_, V> {
+    type Node = &'a mut V;
+
+    fn dsl_node(self) -> &'a mut V {
+        self.0
+    }
+}
+
+/// A list that the items of a `[ .. ]` in [`chart!`] are pushed onto
+#[doc(hidden)]
+pub trait DslPush<V> {
+    fn dsl_push(&mut self, value: V);
+}
+
+impl<T, V: Into<T>> DslPush<V> for Vec<T> {
+    fn dsl_push(&mut self, value: V) {
+        self.push(value.into())
+    }
+}
+
+impl<T, V: Into<T>> DslPush<V> for Option<Vec<T>> {
+    fn dsl_push(&mut self, value: V) {
+        self.get_or_insert_with(Vec::new).push(value.into())
+    }
+}
+
+impl<V: Serialize> DslPush<V> for DatasetData {
+    fn dsl_push(&mut self, value: V) {
+        let value = serde_json::to_value(value).expect("Unable to serialize data.");
+        match &mut self.0 {
+            serde_json::Value::Array(a) => a.push(value),
+            data => *data = serde_json::Value::Array(vec![value]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{bar::Bar, types::*, utils::FnWithArgs};
+
+    #[test]
+    fn readme_example() {
+        let chart = chart!(Bar<NoAnnotations> {
+            id: "bar",
+            data: {
+                labels: ["Mon", "Tue", "Wed"],
+                datasets: [{
+                    label: "Visitors",
+                    data: [XYPoint { x: "Mon", y: 4 }, XYPoint { x: "Tue", y: 8 }],
+                }],
+            },
+            options: {
+                scales: {
+                    "x": { grid: { display: false } },
+                    "y": LinearScale { beginAtZero: true },
+                },
+            },
+        });
+
+        let points = vec![
+            XYPoint {
+                x: "Mon".into(),
+                y: 4.into(),
+                ..Default::default()
+            },
+            XYPoint {
+                x: "Tue".into(),
+                y: 8.into(),
+                ..Default::default()
+            },
+        ];
+        let x = LinearScale {
+            common: ScaleCommon {
+                grid: Some(Grid {
+                    display: Some(false),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let y = LinearScale {
+            beginAtZero: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(chart.id, "bar");
+        assert_eq!(
+            chart.data.labels,
+            Some(vec!["Mon".into(), "Tue".into(), "Wed".into()])
+        );
+        assert_eq!(chart.data.datasets.len(), 1);
+        assert_eq!(chart.data.datasets[0].label, "Visitors");
+        assert_eq!(chart.data.datasets[0].data, points.to_dataset_data());
+        assert_eq!(
+            chart.options.scales,
+            Some(
+                [
+                    ("x".to_string(), ChartScale::Auto(x)),
+                    ("y".to_string(), ChartScale::Linear(y)),
+                ]
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn scale_entries_take_the_common_options() {
+        let options = chart!(ChartOptions<NoAnnotations> {
+            scales: {
+                "x": { display: false, grid: { color: "red" } },
+                "y": LinearScale { beginAtZero: true, common: { position: "right" } },
+            },
+        });
+
+        assert_eq!(
+            serde_json::to_value(options.scales).unwrap(),
+            json!({
+                "x": { "display": false, "grid": { "color": "red" } },
+                "y": { "type": "linear", "position": "right", "beginAtZero": true },
+            })
+        );
+    }
+
+    #[test]
+    fn other_map_entries_are_filled_in() {
+        let plugins = chart!(ChartPlugins<LineAnnotation> {
+            annotation: {
+                annotations: {
+                    "target": { yMin: 10, yMax: 10, borderColor: "red" },
+                },
+            },
+        });
+
+        let annotations = plugins.annotation.unwrap().annotations.unwrap();
+        assert_eq!(annotations["target"].yMin, 10.into());
+        assert_eq!(annotations["target"].borderColor, "red".into());
+    }
+
+    #[test]
+    fn lists_and_functions() {
+        let dataset = chart!(XYDataset {
+            type: "line",
+            data: [1, 2, 3],
+            borderDash: [4, 2],
+            segment: { borderColor: FnWithArgs { body: "return 'red'" } },
+        });
+
+        assert_eq!(dataset.r#type, "line");
+        assert_eq!(
+            serde_json::to_value(&dataset.data).unwrap(),
+            json!([1, 2, 3])
+        );
+        assert_eq!(dataset.borderDash, vec![4.into(), 2.into()]);
+    }
+}
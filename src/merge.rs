@@ -0,0 +1,220 @@
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{bar::Bar, doughnut::Doughnut, pie::Pie, scatter::Scatter, types::*, ChartError};
+
+/// Layers one config over another, the way Chart.js resolves options:
+/// the fields set on the override win, and unset ones (`None`, empty strings or empty lists)
+/// fall through to the base. Nested structs and maps are merged field by field, while lists,
+/// gradients and patterns are replaced as a whole.
+///
+/// Returns an error if the merged config can't be read back into `Self`.
+///
+/// ```rust,ignore
+///   let house_style = ChartOptions::<NoAnnotations> { .. };
+///   let options = house_style.merge(ChartOptions {
+///       maintainAspectRatio: Some(false),
+///       ..Default::default()
+///   })?;
+/// ```
+pub trait Merge: Serialize + DeserializeOwned {
+    fn merge(&self, overrides: Self) -> Result<Self, ChartError>
+    where
+        Self: Sized,
+    {
+        let serde_error = |e: serde_json::Error| ChartError::Serde(e.to_string());
+        let mut base = serde_json::to_value(self).map_err(serde_error)?;
+        let overrides = serde_json::to_value(overrides).map_err(serde_error)?;
+        merge_values(&mut base, overrides);
+
+        serde_json::from_value(base).map_err(serde_error)
+    }
+}
+
+fn merge_values(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (_, Value::Null) => {}
+        (_, Value::String(s)) if s.is_empty() => {}
+        (_, Value::Array(a)) if a.is_empty() => {}
+        // a gradient or a pattern, whose fields only make sense together
+        (base, Value::Object(overrides))
+            if overrides.contains_key("gradient") || overrides.contains_key("pattern") =>
+        {
+            *base = Value::Object(overrides)
+        }
+        (Value::Object(base), Value::Object(overrides)) => {
+            overrides.into_iter().for_each(|(key, value)| {
+                merge_values(base.entry(key).or_insert(Value::Null), value)
+            });
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
+impl<A: Annotation + DeserializeOwned> Merge for ChartOptions<A> {}
+impl<D: DatasetTrait + DeserializeOwned> Merge for Dataset<D> {}
+impl Merge for XYDataset {}
+impl Merge for SinglePointDataset {}
+impl Merge for ChartScale {}
+
+impl<A: Annotation + DeserializeOwned> Merge for Bar<A> {}
+impl<A: Annotation + DeserializeOwned> Merge for Doughnut<A> {}
+impl<A: Annotation + DeserializeOwned> Merge for Pie<A> {}
+impl<A: Annotation + DeserializeOwned> Merge for Scatter<A> {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        color::{Gradient, GradientDirection, Pattern, PatternShape},
+        utils::FnWithArgs,
+        Formatter, Paint,
+    };
+
+    fn options() -> ChartOptions<NoAnnotations> {
+        let y = LinearScale {
+            common: ScaleCommon {
+                position: ScalePosition::Right,
+                grid: Some(Grid {
+                    color: Scriptable::Indexed(vec!["red".into(), "blue".into()]),
+                    lineWidth: Scriptable::Fn(FnWithArgs::new().arg("ctx").body("1")),
+                    ..Default::default()
+                }),
+                border: Some(ScaleBorder {
+                    dash: vec![4.into(), 2.into()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            beginAtZero: Some(true),
+            ticks: Some(ScaleTicks {
+                callback: Formatter::Percent { decimals: 0 }.tick_callback(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let x = TimeScale {
+            time: Some(ScaleTime {
+                unit: TimeUnit::Day,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        ChartOptions {
+            scales: Some(HashMap::from([
+                ("y".to_string(), y.into()),
+                ("x".to_string(), ChartScale::Time(x)),
+            ])),
+            plugins: Some(ChartPlugins {
+                title: Some(Title {
+                    text: "Sales".into(),
+                    ..Default::default()
+                }),
+                tooltip: Some(TooltipPlugins {
+                    callbacks: Some(TooltipCallbacks {
+                        label: Formatter::Fixed { decimals: 1 }.tooltip_label(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            interaction: Some(ChartInteraction {
+                mode: Some(InteractionMode::Index),
+                ..Default::default()
+            }),
+            locale: "de-DE".into(),
+            ..Default::default()
+        }
+    }
+
+    fn dataset() -> XYDataset {
+        XYDataset {
+            label: "Revenue".into(),
+            backgroundColor: Gradient::linear(GradientDirection::ToRight)
+                .stop(0, "red")
+                .stop(1, "blue")
+                .into(),
+            borderColor: Pattern::default().into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn merging_defaults_round_trips() {
+        assert_eq!(options().merge(Default::default()), Ok(options()));
+        assert_eq!(ChartOptions::default().merge(options()), Ok(options()));
+        assert_eq!(dataset().merge(Default::default()), Ok(dataset()));
+        assert_eq!(XYDataset::default().merge(dataset()), Ok(dataset()));
+    }
+
+    #[test]
+    fn overrides_win_field_by_field() {
+        let merged = options()
+            .merge(ChartOptions {
+                maintainAspectRatio: Some(false),
+                plugins: Some(ChartPlugins {
+                    title: Some(Title {
+                        display: Some(true),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let title = merged.plugins.as_ref().unwrap().title.as_ref().unwrap();
+        assert_eq!(title.text, "Sales");
+        assert_eq!(title.display, Some(true));
+        assert_eq!(merged.maintainAspectRatio, Some(false));
+        assert_eq!(merged.scales, options().scales);
+    }
+
+    #[test]
+    fn gradients_and_patterns_are_replaced_whole() {
+        let vertical = Gradient::linear(GradientDirection::ToTop).stop(0, "green");
+        let merged = dataset()
+            .merge(XYDataset {
+                backgroundColor: vertical.clone().into(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(merged.backgroundColor, Paint::Gradient(vertical));
+        assert_eq!(merged.borderColor, Pattern::default().into());
+    }
+
+    #[test]
+    fn gradients_can_be_replaced_by_patterns() {
+        let dots = Pattern::new(PatternShape::Dots, "blue");
+        let merged = dataset()
+            .merge(XYDataset {
+                backgroundColor: dots.clone().into(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(merged.backgroundColor, Paint::Pattern(dots));
+    }
+
+    #[test]
+    fn scale_types_are_overridden() {
+        let base = ChartScale::Time(TimeScale {
+            time: Some(ScaleTime {
+                unit: TimeUnit::Day,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let category = ChartScale::Category(CategoryScale {
+            labels: vec!["a".into()],
+            ..Default::default()
+        });
+
+        assert_eq!(base.merge(category.clone()), Ok(category));
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{color::Rgba, types::*, Color};
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum PaletteKind {
+    /// Distinct colors for unrelated series, repeated when there are more series than colors
+    #[default]
+    Categorical,
+    /// Colors from light to dark for ordered values, spread across the series
+    Sequential,
+    /// Colors from one extreme through a neutral middle to another, spread across the series
+    Diverging,
+}
+
+/// How a series is matched to a color of a [`Palette`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorBy {
+    /// By position, so the first series always gets the first color
+    #[default]
+    Index,
+    /// By a hash of the label, so a series keeps its color when others are added, removed or
+    /// reordered, and has the same color on every chart it's in. When two labels of a chart
+    /// hash to the same color, one of them gets the next free color instead.
+    /// Only categorical palettes can do this, others fall back to [`ColorBy::Index`]
+    Label,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Palette {
+    pub kind: PaletteKind,
+    pub colors: Vec<Color>,
+}
+
+impl Palette {
+    pub fn new<C: Into<Color>>(kind: PaletteKind, colors: impl IntoIterator<Item = C>) -> Self {
+        Self {
+            kind,
+            colors: colors.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// The colors of Chart.js's own `Colors` plugin
+    pub fn chartjs() -> Self {
+        Self::new(
+            PaletteKind::Categorical,
+            [
+                "#36a2eb", "#ff6384", "#ff9f40", "#ffcd56", "#4bc0c0", "#9966ff", "#c9cbcf",
+            ],
+        )
+    }
+
+    /// Okabe & Ito's palette, distinguishable with all common forms of color blindness
+    pub fn okabe_ito() -> Self {
+        Self::new(
+            PaletteKind::Categorical,
+            [
+                "#e69f00", "#56b4e9", "#009e73", "#f0e442", "#0072b2", "#d55e00", "#cc79a7",
+                "#000000",
+            ],
+        )
+    }
+
+    pub fn tableau10() -> Self {
+        Self::new(
+            PaletteKind::Categorical,
+            [
+                "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1",
+                "#ff9da7", "#9c755f", "#bab0ac",
+            ],
+        )
+    }
+
+    /// Perceptually uniform from purple to yellow, and readable with color blindness
+    pub fn viridis() -> Self {
+        Self::new(
+            PaletteKind::Sequential,
+            [
+                "#440154", "#482878", "#3e4a89", "#31688e", "#26828e", "#1f9e89", "#35b779",
+                "#6dcd59", "#b4de2c", "#fde725",
+            ],
+        )
+    }
+
+    pub fn blues() -> Self {
+        Self::new(
+            PaletteKind::Sequential,
+            [
+                "#deebf7", "#c6dbef", "#9ecae1", "#6baed6", "#4292c6", "#2171b5", "#08519c",
+                "#08306b",
+            ],
+        )
+    }
+
+    pub fn red_blue() -> Self {
+        Self::new(
+            PaletteKind::Diverging,
+            [
+                "#b2182b", "#d6604d", "#f4a582", "#fddbc7", "#f7f7f7", "#d1e5f0", "#92c5de",
+                "#4393c3", "#2166ac",
+            ],
+        )
+    }
+
+    /// The color of the series at `index` out of `count` series
+    pub fn color(&self, index: usize, count: usize) -> Color {
+        if self.colors.is_empty() {
+            return Color::default();
+        }
+
+        match self.kind {
+            PaletteKind::Categorical => self.colors[index % self.colors.len()].clone(),
+            PaletteKind::Sequential | PaletteKind::Diverging => {
+                let position = match count {
+                    0 | 1 => 0.5,
+                    _ => index.min(count - 1) as f64 / (count - 1) as f64,
+                };
+                self.sample(position)
+            }
+        }
+    }
+
+    /// The colors for `count` series
+    pub fn colors(&self, count: usize) -> Vec<Color> {
+        (0..count).map(|i| self.color(i, count)).collect()
+    }
+
+    /// The color the hash of `label` picks, the same for every chart and page.
+    /// Two labels can pick the same color, see [`Palette::colors_for_labels`] to avoid that
+    pub fn color_for_label(&self, label: &str, index: usize, count: usize) -> Color {
+        match self.kind {
+            PaletteKind::Categorical if !self.colors.is_empty() => {
+                self.colors[(fnv1a(label) % self.colors.len() as u64) as usize].clone()
+            }
+            _ => self.color(index, count),
+        }
+    }
+
+    /// The colors of the series of one chart with these labels. Each label gets the color
+    /// [`Palette::color_for_label`] picks, unless another label in the chart has it already,
+    /// in which case it gets the next free color. Labels are placed in sorted order, so the
+    /// colors don't depend on the order of the series, and the series have distinct colors
+    /// while there are enough of them
+    pub fn colors_for_labels(&self, labels: &[&str]) -> Vec<Color> {
+        let count = labels.len();
+        if self.kind != PaletteKind::Categorical || self.colors.is_empty() {
+            return self.colors(count);
+        }
+
+        let mut sorted = labels.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        let len = self.colors.len();
+        let mut used = vec![false; len];
+        let slots = sorted
+            .into_iter()
+            .map(|label| {
+                // once every color is taken, they are shared again
+                if used.iter().all(|u| *u) {
+                    used.fill(false);
+                }
+                let start = (fnv1a(label) % len as u64) as usize;
+                let slot = (0..len)
+                    .map(|k| (start + k) % len)
+                    .find(|&slot| !used[slot])
+                    .unwrap();
+                used[slot] = true;
+                (label, slot)
+            })
+            .collect::<HashMap<_, _>>();
+
+        labels
+            .iter()
+            .map(|label| self.colors[slots[label]].clone())
+            .collect()
+    }
+
+    /// Interpolates between the colors, with `position` between `0.0` and `1.0`
+    fn sample(&self, position: f64) -> Color {
+        let scaled = position.clamp(0.0, 1.0) * (self.colors.len() - 1) as f64;
+        let (i, t) = (scaled.floor() as usize, scaled.fract());
+        let from = &self.colors[i];
+        let to = self.colors.get(i + 1).unwrap_or(from);
+
+        match (from.parse(), to.parse()) {
+            (Ok(a), Ok(b)) => {
+                let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+                Rgba::new(
+                    lerp(a.r, b.r),
+                    lerp(a.g, b.g),
+                    lerp(a.b, b.b),
+                    a.a + (b.a - a.a) * t,
+                )
+                .into()
+            }
+            _ => from.clone(),
+        }
+    }
+
+    fn pick(&self, by: ColorBy, labels: &[&str]) -> Vec<Color> {
+        match by {
+            ColorBy::Index => self.colors(labels.len()),
+            ColorBy::Label => self.colors_for_labels(labels),
+        }
+    }
+}
+
+/// A stable hash, unlike `std`'s hashers which may change between Rust versions
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Colors a chart's data from a [`Palette`], leaving any colors that are already set
+pub trait PaletteExt {
+    fn apply_palette(&mut self, palette: &Palette, by: ColorBy);
+}
+
+/// Each dataset gets a color for its line or border, and a translucent one for its fill
+impl PaletteExt for Dataset<Vec<XYDataset>> {
+    fn apply_palette(&mut self, palette: &Palette, by: ColorBy) {
+        let labels = self
+            .datasets
+            .iter()
+            .map(|d| d.label.clone())
+            .collect::<Vec<_>>();
+        let colors = palette.pick(by, &labels.iter().map(String::as_str).collect::<Vec<_>>());
+        self.datasets.iter_mut().zip(colors).for_each(|(d, color)| {
+            if d.borderColor.is_empty() {
+                d.borderColor = color.clone().into();
+            }
+            if d.backgroundColor.is_empty() {
+                d.backgroundColor = color.alpha(0.5).into();
+            }
+        });
+    }
+}
+
+/// Each slice gets a color, matched to the chart's labels
+impl PaletteExt for Dataset<Vec<SinglePointDataset>> {
+    fn apply_palette(&mut self, palette: &Palette, by: ColorBy) {
+        let labels = self.labels.clone().unwrap_or_default();
+        self.datasets.iter_mut().for_each(|d| {
+            if !d.backgroundColor.is_empty() {
+                return;
+            }
+
+            let labels = (0..d.data.len())
+                .map(|i| labels.get(i).map(ToString::to_string).unwrap_or_default())
+                .collect::<Vec<_>>();
+            d.backgroundColor = palette
+                .pick(by, &labels.iter().map(String::as_str).collect::<Vec<_>>())
+                .into_iter()
+                .map(Into::into)
+                .collect();
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_colors_are_distinct() {
+        let palette = Palette::chartjs();
+        let labels = ["a", "b", "c", "d", "e", "f", "g"];
+        let mut colors = palette.colors_for_labels(&labels);
+        colors.sort();
+        colors.dedup();
+        assert_eq!(colors.len(), labels.len());
+    }
+
+    #[test]
+    fn label_colors_are_stable() {
+        let palette = Palette::tableau10();
+        let colors = palette.colors_for_labels(&["Revenue", "Costs", "Profit"]);
+
+        // the order of the series doesn't matter
+        let reordered = palette.colors_for_labels(&["Profit", "Revenue", "Costs"]);
+        assert_eq!(
+            reordered,
+            [colors[2].clone(), colors[0].clone(), colors[1].clone()]
+        );
+
+        // a label gets the color its hash picks, unless that is taken
+        let alone = palette.colors_for_labels(&["Revenue"]);
+        assert_eq!(alone[0], palette.color_for_label("Revenue", 0, 1));
+        // "Costs" is placed first, so always gets the color of its hash
+        assert_eq!(colors[1], palette.color_for_label("Costs", 0, 1));
+    }
+
+    #[test]
+    fn label_colors_are_shared_once_all_are_taken() {
+        let palette = Palette::new(PaletteKind::Categorical, ["red", "blue"]);
+        let colors = palette.colors_for_labels(&["a", "b", "c", "a"]);
+        assert_ne!(colors[0], colors[1]);
+        assert_eq!(colors[0], colors[3]);
+    }
+
+    #[test]
+    fn label_colors_of_other_palettes_are_by_index() {
+        let palette = Palette::viridis();
+        assert_eq!(palette.colors_for_labels(&["x", "y"]), palette.colors(2));
+    }
+}
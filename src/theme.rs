@@ -0,0 +1,322 @@
+use crate::{
+    bar::Bar,
+    defaults::ChartDefaults,
+    doughnut::Doughnut,
+    palette::{ColorBy, Palette, PaletteExt},
+    pie::Pie,
+    scatter::Scatter,
+    types::*,
+    Color,
+};
+
+/// Shared styling for charts, e.g. to support light and dark modes from one chart config.
+///
+/// Applying a theme only fills in fields that are unset, so anything set on
+/// the chart itself wins:
+/// ```rust,ignore
+///   let theme = if dark_mode { Theme::dark() } else { Theme::light() };
+///   chart.apply_theme(&theme);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Theme {
+    pub fontFamily: String,
+
+    pub fontColor: Color,
+
+    pub gridColor: Color,
+
+    /// The color of the line along each scale
+    pub borderColor: Color,
+
+    pub tooltipBackgroundColor: Color,
+
+    pub tooltipTitleColor: Color,
+
+    pub tooltipBodyColor: Color,
+
+    pub palette: Palette,
+
+    pub colorBy: ColorBy,
+
+    /// The border width of datasets
+    pub borderWidth: NumberString,
+}
+
+impl Theme {
+    /// Chart.js's own look
+    pub fn light() -> Self {
+        Self {
+            fontFamily: "'Helvetica Neue', 'Helvetica', 'Arial', sans-serif".into(),
+            fontColor: "#666666".into(),
+            gridColor: "rgba(0, 0, 0, 0.1)".into(),
+            borderColor: "rgba(0, 0, 0, 0.25)".into(),
+            tooltipBackgroundColor: "rgba(0, 0, 0, 0.8)".into(),
+            tooltipTitleColor: "#ffffff".into(),
+            tooltipBodyColor: "#ffffff".into(),
+            palette: Palette::chartjs(),
+            colorBy: ColorBy::Index,
+            borderWidth: 2.into(),
+        }
+    }
+
+    /// Light text and lines, for charts on a dark background
+    pub fn dark() -> Self {
+        Self {
+            fontColor: "#d4d4d4".into(),
+            gridColor: "rgba(255, 255, 255, 0.12)".into(),
+            borderColor: "rgba(255, 255, 255, 0.3)".into(),
+            tooltipBackgroundColor: "rgba(255, 255, 255, 0.9)".into(),
+            tooltipTitleColor: "#1e1e1e".into(),
+            tooltipBodyColor: "#1e1e1e".into(),
+            palette: Palette::tableau10(),
+            ..Self::light()
+        }
+    }
+}
+
+/// Fills in the unset fields of a chart, or part of one, from a [`Theme`]
+pub trait ThemeExt {
+    fn apply_theme(&mut self, theme: &Theme);
+}
+
+fn fill<T: Clone>(field: &mut T, value: &T, is_empty: fn(&T) -> bool) {
+    if is_empty(field) {
+        *field = value.clone();
+    }
+}
+
+/// Only themes the scales in `scales`, while theming a chart also adds its default `x` and `y`
+impl<A: Annotation + Default> ThemeExt for ChartOptions<A> {
+    fn apply_theme(&mut self, theme: &Theme) {
+        fill(&mut self.color, &theme.fontColor, Color::is_empty);
+        self.font
+            .get_or_insert_with(Default::default)
+            .apply_theme(theme);
+
+        let plugins = self.plugins.get_or_insert_with(Default::default);
+        plugins
+            .tooltip
+            .get_or_insert_with(Default::default)
+            .apply_theme(theme);
+        if let Some(title) = &mut plugins.title {
+            title.apply_theme(theme);
+        }
+
+        self.scales
+            .iter_mut()
+            .flat_map(|scales| scales.values_mut())
+            .for_each(|scale| scale.apply_theme(theme));
+    }
+}
+
+/// Themes every chart on the page, except for the colors of datasets
+impl ThemeExt for ChartDefaults {
+    fn apply_theme(&mut self, theme: &Theme) {
+        fill(&mut self.color, &theme.fontColor, Color::is_empty);
+        self.font
+            .get_or_insert_with(Default::default)
+            .apply_theme(theme);
+        self.plugins
+            .get_or_insert_with(Default::default)
+            .tooltip
+            .get_or_insert_with(Default::default)
+            .apply_theme(theme);
+        self.scale
+            .get_or_insert_with(Default::default)
+            .apply_theme(theme);
+    }
+}
+
+impl ThemeExt for Font {
+    fn apply_theme(&mut self, theme: &Theme) {
+        fill(&mut self.family, &theme.fontFamily, String::is_empty);
+    }
+}
+
+impl ThemeExt for Title {
+    fn apply_theme(&mut self, theme: &Theme) {
+        fill(&mut self.color, &theme.fontColor, Color::is_empty);
+    }
+}
+
+impl ThemeExt for TooltipPlugins {
+    fn apply_theme(&mut self, theme: &Theme) {
+        fill(
+            &mut self.backgroundColor,
+            &theme.tooltipBackgroundColor,
+            Color::is_empty,
+        );
+        fill(
+            &mut self.titleColor,
+            &theme.tooltipTitleColor,
+            Color::is_empty,
+        );
+        fill(
+            &mut self.bodyColor,
+            &theme.tooltipBodyColor,
+            Color::is_empty,
+        );
+    }
+}
+
+impl ThemeExt for ChartScale {
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.common_mut().apply_theme(theme);
+        self.ticks_mut().apply_theme(theme);
+    }
+}
+
+impl ThemeExt for ScaleTicks {
+    fn apply_theme(&mut self, theme: &Theme) {
+        fill(&mut self.color, &theme.fontColor, Color::is_empty);
+        if let Some(font) = &mut self.font {
+            font.apply_theme(theme);
+        }
+    }
+}
+
+impl ThemeExt for ScaleCommon {
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.grid
+            .get_or_insert_with(Default::default)
+            .apply_theme(theme);
+        self.border
+            .get_or_insert_with(Default::default)
+            .apply_theme(theme);
+        if let Some(title) = &mut self.title {
+            title.apply_theme(theme);
+        }
+    }
+}
+
+impl ThemeExt for Grid {
+    fn apply_theme(&mut self, theme: &Theme) {
+        fill(
+            &mut self.color,
+            &theme.gridColor.clone().into(),
+            Scriptable::is_empty,
+        );
+    }
+}
+
+impl ThemeExt for ScaleBorder {
+    fn apply_theme(&mut self, theme: &Theme) {
+        fill(&mut self.color, &theme.borderColor, Color::is_empty);
+    }
+}
+
+impl ThemeExt for Dataset<Vec<XYDataset>> {
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.apply_palette(&theme.palette, theme.colorBy);
+        self.datasets.iter_mut().for_each(|d| {
+            fill(
+                &mut d.borderWidth,
+                &theme.borderWidth,
+                NumberString::is_empty,
+            )
+        });
+    }
+}
+
+impl ThemeExt for Dataset<Vec<SinglePointDataset>> {
+    fn apply_theme(&mut self, theme: &Theme) {
+        self.apply_palette(&theme.palette, theme.colorBy);
+        self.datasets.iter_mut().for_each(|d| {
+            fill(
+                &mut d.borderWidth,
+                &theme.borderWidth,
+                NumberString::is_empty,
+            )
+        });
+    }
+}
+
+macro_rules! impl_theme_ext {
+    ($($chart:ident: [$($axis:literal),*]),*) => {
+        $(
+            impl<A: Annotation + Default> ThemeExt for $chart<A> {
+                fn apply_theme(&mut self, theme: &Theme) {
+                    // the default axes would otherwise keep Chart.js's colors
+                    $(self
+                        .options
+                        .scales
+                        .get_or_insert_with(Default::default)
+                        .entry($axis.into())
+                        .or_default();)*
+
+                    self.options.apply_theme(theme);
+                    self.data.apply_theme(theme);
+                }
+            }
+        )*
+    };
+}
+impl_theme_ext!(Bar: ["x", "y"], Scatter: ["x", "y"], Doughnut: [], Pie: []);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_color(chart: &Bar<NoAnnotations>, id: &str) -> Scriptable<Color> {
+        let scales = chart.options.scales.as_ref().unwrap();
+        scales[id].common().grid.as_ref().unwrap().color.clone()
+    }
+
+    #[test]
+    fn default_axes_are_themed() {
+        let mut chart = Bar::<NoAnnotations>::default();
+        chart.apply_theme(&Theme::dark());
+
+        let dark_grid = Scriptable::from(Theme::dark().gridColor);
+        assert_eq!(grid_color(&chart, "x"), dark_grid);
+        assert_eq!(grid_color(&chart, "y"), dark_grid);
+
+        let mut pie = Pie::<NoAnnotations>::default();
+        pie.apply_theme(&Theme::dark());
+        assert_eq!(pie.options.scales, None);
+    }
+
+    #[test]
+    fn explicit_values_are_kept() {
+        let mut chart = Bar::<NoAnnotations>::builder()
+            .id("bar")
+            .title("Sales")
+            .scale("x", |s| {
+                s.with(|s| {
+                    s.common_mut().grid = Some(Grid {
+                        color: Scriptable::from(Color::from("red")),
+                        ..Default::default()
+                    })
+                })
+            })
+            .dataset(|d| d.color("#00ff00").border_width(5))
+            .dataset(|d| d.label("unset"))
+            .options(|o| {
+                o.color = "pink".into();
+                o.plugins.get_or_insert_with(Default::default).title = Some(Title {
+                    text: "Sales".into(),
+                    color: "blue".into(),
+                    ..Default::default()
+                });
+            })
+            .build()
+            .unwrap();
+        chart.apply_theme(&Theme::dark());
+
+        assert_eq!(
+            grid_color(&chart, "x"),
+            Scriptable::from(Color::from("red"))
+        );
+        assert_eq!(chart.options.color, Color::from("pink"));
+        let plugins = chart.options.plugins.as_ref().unwrap();
+        assert_eq!(plugins.title.as_ref().unwrap().color, Color::from("blue"));
+
+        let [green, unset] = &chart.data.datasets[..] else {
+            panic!("expected two datasets");
+        };
+        assert_eq!(green.borderColor, Color::from("#00ff00").into());
+        assert_eq!(green.borderWidth, 5.into());
+        assert!(!unset.borderColor.is_empty());
+        assert_eq!(unset.borderWidth, Theme::dark().borderWidth);
+    }
+}
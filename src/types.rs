@@ -1,30 +1,172 @@
 use std::fmt::Debug;
 
 use {
-    crate::utils::FnWithArgs,
+    crate::{utils::FnWithArgs, Color, Paint},
     serde::{Deserialize, Serialize},
-    std::{collections::HashMap, fmt::Display, option::Option},
+    std::{
+        collections::{BTreeMap, HashMap},
+        fmt::Display,
+        option::Option,
+    },
 };
 
+/// An enum of the values of a Chart.js option, with `Custom` for values that aren't
+/// listed, e.g. ones added by plugins. `Custom("")` is the default unless another variant
+/// is given with `= Variant`, and isn't serialized, like an empty `String` field.
+/// Unlisted values must be strings when deserializing, or numbers if a `; Variant(Type)`
+/// for them is given after the listed values.
+macro_rules! option_enum {
+    (
+        $(#[$meta:meta])* $name:ident $(= $default:ident)? {
+            $($(#[$vmeta:meta])* $variant:ident => $value:literal),* $(,)?
+            $(; $(#[$nmeta:meta])* $number:ident($number_ty:ty) $(,)?)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum $name {
+            $($(#[$vmeta])* $variant,)*
+            $($(#[$nmeta])* $number($number_ty),)?
+            Custom(String),
+        }
+        impl $name {
+            pub fn is_empty(&self) -> bool {
+                matches!(self, Self::Custom(s) if s.is_empty())
+            }
+
+            /// The listed variant that serializes to `value`, if any
+            fn variant(value: &serde_json::Value) -> Option<Self> {
+                $(
+                    if *value == serde_json::json!($value) {
+                        return Some(Self::$variant);
+                    }
+                )*
+                None
+            }
+        }
+        impl Default for $name {
+            fn default() -> Self {
+                option_enum!(@default $($default)?)
+            }
+        }
+        impl From<&str> for $name {
+            fn from(s: &str) -> Self {
+                s.to_string().into()
+            }
+        }
+        impl From<String> for $name {
+            fn from(s: String) -> Self {
+                Self::variant(&serde_json::Value::String(s.clone())).unwrap_or(Self::Custom(s))
+            }
+        }
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                match self {
+                    $(Self::$variant => $value.serialize(serializer),)*
+                    $(Self::$number(n) => n.serialize(serializer),)?
+                    Self::Custom(s) => serializer.serialize_str(s),
+                }
+            }
+        }
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                // other values would be read back as strings, which Chart.js treats differently
+                let value = serde_json::Value::deserialize(deserializer)?;
+                match (Self::variant(&value), value) {
+                    (Some(variant), _) => Ok(variant),
+                    (None, serde_json::Value::String(s)) => Ok(Self::Custom(s)),
+                    $((None, value @ serde_json::Value::Number(_)) => serde_json::from_value(value)
+                        .map(Self::$number)
+                        .map_err(serde::de::Error::custom),)?
+                    (None, value) => Err(serde::de::Error::custom(format!(
+                        "expected a string for {}, found `{value}`",
+                        stringify!($name)
+                    ))),
+                }
+            }
+        }
+    };
+    (@default) => {
+        Self::Custom(String::new())
+    };
+    (@default $default:ident) => {
+        Self::$default
+    };
+}
+
 pub trait DatasetTrait: Serialize {}
-pub trait DatasetDataExt: Serialize {
+pub trait DatasetDataExt {
     fn to_dataset_data(self) -> DatasetData
     where
         Self: Sized + Serialize,
     {
-        DatasetData(serde_json::to_value(self).unwrap())
+        DatasetData::from_serialize(self)
+    }
+}
+/// A value that is drawn as one point of a dataset, usually implemented with `#[derive(ChartData)]`
+pub trait ChartPoint {
+    fn to_xy_point(&self) -> XYPoint;
+}
+/// [`DatasetDataExt`] for lists of [`ChartPoint`]s, which don't need to be `Serialize`
+pub trait ChartPointsExt {
+    fn to_dataset_data(self) -> DatasetData;
+}
+impl<T: ChartPoint> ChartPointsExt for Vec<T> {
+    fn to_dataset_data(self) -> DatasetData {
+        self.as_slice().to_dataset_data()
+    }
+}
+impl<T: ChartPoint> ChartPointsExt for &[T] {
+    fn to_dataset_data(self) -> DatasetData {
+        DatasetData::from_serialize(self.iter().map(T::to_xy_point).collect::<Vec<_>>())
     }
 }
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
 #[serde(transparent)]
-pub struct DatasetData(serde_json::Value);
+pub struct DatasetData(pub(crate) serde_json::Value);
 impl DatasetData {
+    /// For implementing [`DatasetDataExt`] on types that serialize to Chart.js's data format
+    pub fn from_serialize(data: impl Serialize) -> Self {
+        DatasetData(serde_json::to_value(data).unwrap())
+    }
+
     fn is_empty(&self) -> bool {
-        serde_json::to_value(self)
-            .unwrap()
-            .as_array()
-            .unwrap()
-            .is_empty()
+        match &self.0 {
+            serde_json::Value::Null => true,
+            serde_json::Value::Array(a) => a.is_empty(),
+            _ => false,
+        }
+    }
+}
+impl<T: ChartPoint> From<Vec<T>> for DatasetData {
+    fn from(points: Vec<T>) -> Self {
+        points.to_dataset_data()
+    }
+}
+impl<T: ChartPoint> From<&[T]> for DatasetData {
+    fn from(points: &[T]) -> Self {
+        points.to_dataset_data()
+    }
+}
+impl From<Vec<XYPoint>> for DatasetData {
+    fn from(points: Vec<XYPoint>) -> Self {
+        points.to_dataset_data()
+    }
+}
+impl From<Vec<MinMaxPoint>> for DatasetData {
+    fn from(points: Vec<MinMaxPoint>) -> Self {
+        points.to_dataset_data()
+    }
+}
+impl<T: Serialize> From<Rows<T>> for DatasetData {
+    fn from(rows: Rows<T>) -> Self {
+        rows.to_dataset_data()
     }
 }
 impl PartialOrd for DatasetData {
@@ -43,7 +185,7 @@ pub trait Annotation: Serialize {}
 #[derive(Debug, Deserialize, Serialize, Default)]
 pub struct NoDatasets {}
 impl DatasetTrait for NoDatasets {}
-#[derive(Debug, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
 pub struct NoAnnotations {}
 impl Annotation for NoAnnotations {}
 
@@ -57,6 +199,8 @@ pub struct Dataset<D: DatasetTrait> {
 enum StringOrInt {
     String(String),
     Int(isize),
+    Float(f64),
+    Bool(bool),
     Vec(Vec<()>),
 }
 impl ToString for StringOrInt {
@@ -64,10 +208,18 @@ impl ToString for StringOrInt {
         match self {
             StringOrInt::String(s) => s.to_string(),
             StringOrInt::Int(i) => format!("{i}"),
+            StringOrInt::Float(f) => format!("{f}"),
+            StringOrInt::Bool(b) => format!("{b}"),
             StringOrInt::Vec(_) => Default::default(),
         }
     }
 }
+/// A number, or a date as a string.
+///
+/// Anything `Display` converts with `.into()`, including dates, but that uses their `Display`
+/// format, e.g. `2024-01-01 00:00:00 UTC` for a `chrono::DateTime`, which date adapters may
+/// not read. Use [`NumberOrDateString::from_date`] for anything implementing
+/// [`ChartDate`](crate::ChartDate) instead.
 #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct NumberOrDateString(String);
 impl From<NumberString> for NumberOrDateString {
@@ -147,7 +299,7 @@ impl<'de> Deserialize<'de> for BoolString {
     }
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct NumberString(String);
 impl From<NumberOrDateString> for NumberString {
     fn from(value: NumberOrDateString) -> Self {
@@ -195,7 +347,7 @@ impl<'de> Deserialize<'de> for NumberString {
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SinglePointDataset {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
-    pub backgroundColor: Vec<String>,
+    pub backgroundColor: Vec<Paint>,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub base: NumberString,
@@ -206,11 +358,11 @@ pub struct SinglePointDataset {
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub barPercentage: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub borderColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub borderColor: Paint,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub borderSkipped: String,
+    #[serde(skip_serializing_if = "BorderSkipped::is_empty", default)]
+    pub borderSkipped: BorderSkipped,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub borderWidth: NumberString,
@@ -233,11 +385,11 @@ pub struct SinglePointDataset {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grouped: Option<bool>,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub hoverBackgroundColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub hoverBackgroundColor: Paint,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub hoverBorderColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub hoverBorderColor: Paint,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub hoverBorderWidth: NumberString,
@@ -245,8 +397,8 @@ pub struct SinglePointDataset {
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub hoverBorderRadius: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub indexAxis: String,
+    #[serde(skip_serializing_if = "IndexAxis::is_empty", default)]
+    pub indexAxis: IndexAxis,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub inflateAmount: NumberString,
@@ -263,17 +415,17 @@ pub struct SinglePointDataset {
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub order: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub pointBackgroundColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub pointBackgroundColor: Paint,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub pointBorderColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub pointBorderColor: Paint,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub pointBorderWidth: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub pointHoverBackgroundColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub pointHoverBackgroundColor: Paint,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub pointHoverBorderWidth: NumberString,
@@ -284,8 +436,8 @@ pub struct SinglePointDataset {
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub pointRadius: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub pointStyle: String,
+    #[serde(skip_serializing_if = "PointStyle::is_empty", default)]
+    pub pointStyle: PointStyle,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub datalabels: Option<DataLabels>,
@@ -313,14 +465,14 @@ impl DatasetTrait for Vec<SinglePointDataset> {}
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct XYDataset {
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub backgroundColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub backgroundColor: Paint,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub barThickness: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub borderColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub borderColor: Paint,
 
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub borderDash: Vec<NumberString>,
@@ -343,8 +495,8 @@ pub struct XYDataset {
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub category_label: String,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub hoverBackgroundColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub hoverBackgroundColor: Paint,
 
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub label: String,
@@ -352,17 +504,17 @@ pub struct XYDataset {
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub order: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub pointBackgroundColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub pointBackgroundColor: Paint,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub pointBorderColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub pointBorderColor: Paint,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub pointBorderWidth: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub pointHoverBackgroundColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub pointHoverBackgroundColor: Paint,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub pointHoverBorderWidth: NumberString,
@@ -379,8 +531,8 @@ pub struct XYDataset {
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub hitRadius: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub pointStyle: String,
+    #[serde(skip_serializing_if = "PointStyle::is_empty", default)]
+    pub pointStyle: PointStyle,
 
     #[serde(skip_serializing_if = "String::is_empty", default)]
     #[serde(rename = "type")]
@@ -398,8 +550,8 @@ pub struct XYDataset {
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub yAxisID: String,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub fill: String,
+    #[serde(skip_serializing_if = "FillTarget::is_empty", default)]
+    pub fill: FillTarget,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub base: NumberString,
@@ -407,8 +559,8 @@ pub struct XYDataset {
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub barPercentage: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub borderSkipped: String,
+    #[serde(skip_serializing_if = "BorderSkipped::is_empty", default)]
+    pub borderSkipped: BorderSkipped,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub borderRadius: NumberString,
@@ -422,8 +574,8 @@ pub struct XYDataset {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grouped: Option<bool>,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub hoverBorderColor: String,
+    #[serde(skip_serializing_if = "Paint::is_empty", default)]
+    pub hoverBorderColor: Paint,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub hoverBorderWidth: NumberString,
@@ -431,8 +583,8 @@ pub struct XYDataset {
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub hoverBorderRadius: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub indexAxis: String,
+    #[serde(skip_serializing_if = "IndexAxis::is_empty", default)]
+    pub indexAxis: IndexAxis,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub inflateAmount: NumberString,
@@ -457,9 +609,47 @@ pub struct XYDataset {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub spanGaps: Option<bool>,
+
+    /// Which properties of the objects in `data` are the x and y values, see [`Rows`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parsing: Option<Parsing>,
 }
 impl DatasetTrait for Vec<XYDataset> {}
 
+/// The keys Chart.js reads the values of each data object from. Nested properties are
+/// separated by dots, e.g. `"stats.mean"`, and a dot within a key is escaped with a backslash,
+/// e.g. `"version\\.major"`
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Parsing {
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub xAxisKey: String,
+
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub yAxisKey: String,
+}
+impl Parsing {
+    pub fn new(x_key: impl Into<String>, y_key: impl Into<String>) -> Self {
+        Self {
+            xAxisKey: x_key.into(),
+            yAxisKey: y_key.into(),
+        }
+    }
+}
+
+/// Any serializable rows, e.g. an API response, passed to Chart.js as they are.
+/// Set the dataset's [`Parsing`] to the keys of the x and y values:
+/// ```rust,ignore
+///   XYDataset {
+///       data: Rows(readings).to_dataset_data(),
+///       parsing: Some(Parsing::new("recordedAt", "sensor.value")),
+///       ..Default::default()
+///   }
+/// ```
+#[derive(Debug, Clone, Serialize, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Rows<T: Serialize>(pub Vec<T>);
+impl<T: Serialize> DatasetDataExt for Rows<T> {}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct XYPoint {
     #[serde(skip_serializing_if = "NumberOrDateString::is_empty", default)]
@@ -468,6 +658,10 @@ pub struct XYPoint {
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub y: NumberString,
 
+    /// The radius of the point on a bubble chart
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub r: NumberString,
+
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub description: String,
 }
@@ -531,6 +725,18 @@ pub struct ChartOptions<A: Annotation> {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub responsive: Option<bool>,
+
+    /// The default color of text, unless set on the element itself
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub color: Color,
+
+    /// The default font of text, unless set on the element itself
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font: Option<Font>,
+
+    /// The language numbers are formatted in
+    #[serde(skip_serializing_if = "Locale::is_empty", default)]
+    pub locale: Locale,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -580,8 +786,8 @@ pub struct TooltipPlugins {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub bodyColor: String,
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub bodyColor: Color,
 
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub bodyAlign: String,
@@ -589,39 +795,261 @@ pub struct TooltipPlugins {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub displayColors: Option<bool>,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub backgroundColor: String,
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub backgroundColor: Color,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub titleColor: String,
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub titleColor: Color,
 
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub titleAlign: String,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub titleMarginBottom: NumberString,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callbacks: Option<TooltipCallbacks>,
 }
 
+/// Functions that return the text of a tooltip, called with the tooltip as `this`
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
-pub struct ChartScale {
-    #[serde(rename = "type")]
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub r#type: String,
+pub struct TooltipCallbacks {
+    /// Called with the `tooltipItems` of the tooltip
+    #[serde(skip_serializing_if = "FnWithArgs::is_empty", default)]
+    pub title: FnWithArgs,
+
+    /// Called with the `tooltipItem` of each line
+    #[serde(skip_serializing_if = "FnWithArgs::is_empty", default)]
+    pub label: FnWithArgs,
+
+    /// Called with the `tooltipItem` of each line
+    #[serde(skip_serializing_if = "FnWithArgs::is_empty", default)]
+    pub afterLabel: FnWithArgs,
+
+    /// Called with the `tooltipItems` of the tooltip
+    #[serde(skip_serializing_if = "FnWithArgs::is_empty", default)]
+    pub footer: FnWithArgs,
+}
+
+/// A scale, with the options that apply to its type
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChartScale {
+    /// No `type`, so Chart.js picks the default for the axis, e.g. linear for the value axis of a bar chart
+    Auto(LinearScale),
+    Linear(LinearScale),
+    Logarithmic(LogarithmicScale),
+    Category(CategoryScale),
+    Time(TimeScale),
+    TimeSeries(TimeScale),
+    /// A type added by a plugin
+    Custom(String, LinearScale),
+}
+impl Default for ChartScale {
+    fn default() -> Self {
+        Self::Auto(Default::default())
+    }
+}
+impl ChartScale {
+    pub fn r#type(&self) -> ScaleType {
+        match self {
+            Self::Auto(_) => ScaleType::default(),
+            Self::Linear(_) => ScaleType::Linear,
+            Self::Logarithmic(_) => ScaleType::Logarithmic,
+            Self::Category(_) => ScaleType::Category,
+            Self::Time(_) => ScaleType::Time,
+            Self::TimeSeries(_) => ScaleType::TimeSeries,
+            Self::Custom(r#type, _) => r#type.as_str().into(),
+        }
+    }
+
+    pub fn common(&self) -> &ScaleCommon {
+        match self {
+            Self::Auto(s) | Self::Linear(s) | Self::Custom(_, s) => &s.common,
+            Self::Logarithmic(s) => &s.common,
+            Self::Category(s) => &s.common,
+            Self::Time(s) | Self::TimeSeries(s) => &s.common,
+        }
+    }
+
+    pub fn common_mut(&mut self) -> &mut ScaleCommon {
+        match self {
+            Self::Auto(s) | Self::Linear(s) | Self::Custom(_, s) => &mut s.common,
+            Self::Logarithmic(s) => &mut s.common,
+            Self::Category(s) => &mut s.common,
+            Self::Time(s) | Self::TimeSeries(s) => &mut s.common,
+        }
+    }
+
+    /// The tick options every type of scale has
+    pub fn ticks_mut(&mut self) -> &mut ScaleTicks {
+        match self {
+            Self::Auto(s) | Self::Linear(s) | Self::Custom(_, s) => {
+                s.ticks.get_or_insert_with(Default::default)
+            }
+            Self::Logarithmic(s) => s.ticks.get_or_insert_with(Default::default),
+            Self::Category(s) => s.ticks.get_or_insert_with(Default::default),
+            Self::Time(s) | Self::TimeSeries(s) => {
+                &mut s.ticks.get_or_insert_with(Default::default).ticks
+            }
+        }
+    }
+
+    /// Changes the type of the scale, keeping the options the new type also has:
+    /// `common` and `ticks` for every type, and `beginAtZero` and `grace` for linear ones
+    pub fn with_type(self, r#type: ScaleType) -> Self {
+        if r#type == self.r#type() {
+            return self;
+        }
+        let time = match &self {
+            Self::Time(s) | Self::TimeSeries(s) => s.clone(),
+            _ => TimeScale::default(),
+        };
+        let linear = self.into_linear();
+        let time = TimeScale {
+            common: linear.common.clone(),
+            ticks: linear.ticks.clone().map(|ticks| TimeTicks {
+                ticks,
+                ..time.ticks.unwrap_or_default()
+            }),
+            ..time
+        };
+        match r#type {
+            ScaleType::Logarithmic => Self::Logarithmic(LogarithmicScale {
+                common: linear.common,
+                ticks: linear.ticks,
+            }),
+            ScaleType::Category => Self::Category(CategoryScale {
+                common: linear.common,
+                ticks: linear.ticks,
+                ..Default::default()
+            }),
+            ScaleType::Time => Self::Time(time),
+            ScaleType::TimeSeries => Self::TimeSeries(time),
+            r#type => Self::from_linear(r#type, linear),
+        }
+    }
+
+    /// Whether the scale has the options of a linear scale, like `beginAtZero`
+    pub fn is_linear(&self) -> bool {
+        matches!(self, Self::Auto(_) | Self::Linear(_) | Self::Custom(..))
+    }
+
+    /// The options of the scale that a linear scale also has
+    fn into_linear(self) -> LinearScale {
+        match self {
+            Self::Auto(s) | Self::Linear(s) | Self::Custom(_, s) => s,
+            Self::Logarithmic(LogarithmicScale { common, ticks })
+            | Self::Category(CategoryScale { common, ticks, .. }) => LinearScale {
+                common,
+                ticks,
+                ..Default::default()
+            },
+            Self::Time(s) | Self::TimeSeries(s) => LinearScale {
+                common: s.common,
+                ticks: s.ticks.map(|t| t.ticks),
+                ..Default::default()
+            },
+        }
+    }
 
+    /// The scales that have the options of a linear scale
+    fn from_linear(r#type: ScaleType, scale: LinearScale) -> Self {
+        match r#type {
+            ScaleType::Linear => Self::Linear(scale),
+            ScaleType::RadialLinear => Self::Custom("radialLinear".into(), scale),
+            ScaleType::Custom(r#type) if !r#type.is_empty() => Self::Custom(r#type, scale),
+            _ => Self::Auto(scale),
+        }
+    }
+}
+impl From<LinearScale> for ChartScale {
+    fn from(scale: LinearScale) -> Self {
+        Self::Linear(scale)
+    }
+}
+impl From<LogarithmicScale> for ChartScale {
+    fn from(scale: LogarithmicScale) -> Self {
+        Self::Logarithmic(scale)
+    }
+}
+impl From<CategoryScale> for ChartScale {
+    fn from(scale: CategoryScale) -> Self {
+        Self::Category(scale)
+    }
+}
+impl From<TimeScale> for ChartScale {
+    fn from(scale: TimeScale) -> Self {
+        Self::Time(scale)
+    }
+}
+impl Serialize for ChartScale {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let options = match self {
+            Self::Auto(s) | Self::Linear(s) | Self::Custom(_, s) => serde_json::to_value(s),
+            Self::Logarithmic(s) => serde_json::to_value(s),
+            Self::Category(s) => serde_json::to_value(s),
+            Self::Time(s) | Self::TimeSeries(s) => serde_json::to_value(s),
+        };
+        let mut options = options.map_err(serde::ser::Error::custom)?;
+
+        let r#type = self.r#type();
+        if let (Some(options), false) = (options.as_object_mut(), r#type.is_empty()) {
+            options.insert(
+                "type".into(),
+                serde_json::to_value(r#type).map_err(serde::ser::Error::custom)?,
+            );
+        }
+        options.serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for ChartScale {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let mut options = serde_json::Value::deserialize(deserializer)?;
+        let r#type = options
+            .as_object_mut()
+            .and_then(|o| o.remove("type"))
+            .map(ScaleType::deserialize)
+            .transpose()
+            .map_err(D::Error::custom)?
+            .unwrap_or_default();
+
+        let scale = match r#type {
+            ScaleType::Logarithmic => serde_json::from_value(options).map(Self::Logarithmic),
+            ScaleType::Category => serde_json::from_value(options).map(Self::Category),
+            ScaleType::Time => serde_json::from_value(options).map(Self::Time),
+            ScaleType::TimeSeries => serde_json::from_value(options).map(Self::TimeSeries),
+            r#type => serde_json::from_value(options).map(|s| Self::from_linear(r#type, s)),
+        };
+        scale.map_err(D::Error::custom)
+    }
+}
+/// The options every type of cartesian scale has
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ScaleCommon {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub alignToPixels: Option<bool>,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub backgroundColour: String,
+    /// Whether this is an x or a y axis, which Chart.js otherwise works out from the
+    /// scale's `position`, or the first letter of its id
+    #[serde(skip_serializing_if = "IndexAxis::is_empty", default)]
+    pub axis: IndexAxis,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub beginAtZero: Option<bool>,
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub backgroundColor: Color,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub border: Option<ScaleBorder>,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub bounds: String,
+    #[serde(skip_serializing_if = "Bounds::is_empty", default)]
+    pub bounds: Bounds,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display: Option<bool>,
@@ -629,21 +1057,9 @@ pub struct ChartScale {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reverse: Option<bool>,
 
-    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
-    pub barPercentage: NumberString,
-
-    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
-    pub categoryPercentage: NumberString,
-
-    #[serde(skip_serializing_if = "NumberOrDateString::is_empty", default)]
-    pub grace: NumberOrDateString,
-
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grid: Option<Grid>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub grouped: Option<bool>,
-
     #[serde(skip_serializing_if = "Option::is_none")]
     pub offset: Option<bool>,
 
@@ -653,8 +1069,8 @@ pub struct ChartScale {
     #[serde(skip_serializing_if = "NumberOrDateString::is_empty", default)]
     pub min: NumberOrDateString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub position: String,
+    #[serde(skip_serializing_if = "ScalePosition::is_empty", default)]
+    pub position: ScalePosition,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stacked: Option<bool>,
@@ -665,17 +1081,97 @@ pub struct ChartScale {
     #[serde(skip_serializing_if = "NumberOrDateString::is_empty", default)]
     pub suggestedMin: NumberOrDateString,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<Title>,
+
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub weight: NumberString,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LinearScale {
+    #[serde(flatten)]
+    pub common: ScaleCommon,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beginAtZero: Option<bool>,
+
+    /// Space added above and below the data, as a number or a percentage of the range, e.g. `"5%"`
+    #[serde(skip_serializing_if = "NumberOrDateString::is_empty", default)]
+    pub grace: NumberOrDateString,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ticks: Option<ScaleTicks>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LogarithmicScale {
+    #[serde(flatten)]
+    pub common: ScaleCommon,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticks: Option<ScaleTicks>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CategoryScale {
+    #[serde(flatten)]
+    pub common: ScaleCommon,
+
+    /// The labels of this scale, instead of the chart's
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub labels: Vec<NumberOrDateString>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticks: Option<ScaleTicks>,
+}
+
+/// A time scale, or a time series scale, which spaces the data evenly.
+/// Needs a date adapter, e.g. `chartjs-adapter-date-fns`
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeScale {
+    #[serde(flatten)]
+    pub common: ScaleCommon,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adapters: Option<ScaleAdapters>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time: Option<ScaleTime>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub title: Option<Title>,
+    pub ticks: Option<TimeTicks>,
 
-    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
-    pub weight: NumberString,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offsetAfterAutoskip: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ScaleAdapters {
+    /// Passed to the date adapter, e.g. `{ "zone": "UTC" }` for `chartjs-adapter-luxon`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub date: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeTicks {
+    #[serde(flatten)]
+    pub ticks: ScaleTicks,
+
+    #[serde(skip_serializing_if = "TicksSource::is_empty", default)]
+    pub source: TicksSource,
+}
+
+option_enum! {
+    /// Where the ticks of a time scale come from
+    TicksSource {
+        /// Ticks at a unit that fits the scale
+        Auto => "auto",
+        /// A tick for each data point
+        Data => "data",
+        /// A tick for each label
+        Labels => "labels",
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -683,14 +1179,15 @@ pub struct ScaleBorder {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display: Option<bool>,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub color: String,
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub color: Color,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub width: NumberString,
 
-    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
-    pub dash: NumberString,
+    /// The lengths of the dashes and gaps of the line, e.g. `[4, 2]`
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub dash: Vec<NumberString>,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub dashOffset: NumberString,
@@ -706,7 +1203,80 @@ pub struct Grid {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub drawOnChartArea: Option<bool>,
+
+    /// Draws the lines next to the ticks, outside the chart area
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drawTicks: Option<bool>,
+
+    #[serde(skip_serializing_if = "Scriptable::is_empty", default)]
+    pub color: Scriptable<Color>,
+
+    #[serde(skip_serializing_if = "Scriptable::is_empty", default)]
+    pub lineWidth: Scriptable<NumberString>,
+
+    /// How far the lines next to the ticks reach out of the chart area
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub tickLength: NumberString,
+
+    #[serde(skip_serializing_if = "Scriptable::is_empty", default)]
+    pub tickColor: Scriptable<Color>,
+
+    #[serde(skip_serializing_if = "Scriptable::is_empty", default)]
+    pub tickWidth: Scriptable<NumberString>,
+
+    /// Puts the lines between the labels rather than on them, the default for bar charts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<bool>,
+
+    /// Draws circles rather than polygons on radial scales
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circular: Option<bool>,
+
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub z: NumberString,
+}
+
+/// An option that can be one value, a value for each index, e.g. each line of a grid,
+/// or a function that returns the value, called with the `context` of each element
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(untagged)]
+pub enum Scriptable<T> {
+    Fn(FnWithArgs),
+    Indexed(Vec<T>),
+    Value(T),
+}
+impl<T: Default> Default for Scriptable<T> {
+    fn default() -> Self {
+        Self::Value(T::default())
+    }
+}
+impl<T: Default + PartialEq> Scriptable<T> {
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Value(v) if *v == T::default())
+    }
 }
+impl<T> From<FnWithArgs> for Scriptable<T> {
+    fn from(f: FnWithArgs) -> Self {
+        Self::Fn(f)
+    }
+}
+macro_rules! scriptable_from {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Scriptable<$t> {
+                fn from(value: $t) -> Self {
+                    Self::Value(value)
+                }
+            }
+            impl<D: Display> From<D> for Scriptable<$t> {
+                fn from(value: D) -> Self {
+                    Self::Value(value.into())
+                }
+            }
+        )*
+    };
+}
+scriptable_from!(Color, NumberString);
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct LineAnnotation {
@@ -729,8 +1299,8 @@ pub struct LineAnnotation {
     #[serde(skip_serializing_if = "NumberOrDateString::is_empty", default)]
     pub yMax: NumberOrDateString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub borderColor: String,
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub borderColor: Color,
 
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub borderDash: Vec<NumberString>,
@@ -763,11 +1333,11 @@ pub struct BoxAnnotation {
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub yMax: String,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub borderColor: String,
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub borderColor: Color,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub backgroundColor: String,
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub backgroundColor: Color,
 
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub borderDash: Vec<NumberString>,
@@ -781,8 +1351,46 @@ impl Annotation for BoxAnnotation {}
 pub struct ScaleTime {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub displayFormats: Option<DisplayFormats>,
+
+    #[serde(skip_serializing_if = "TimeUnit::is_empty", default)]
+    pub unit: TimeUnit,
+
+    #[serde(skip_serializing_if = "TimeUnit::is_empty", default)]
+    pub minUnit: TimeUnit,
+
+    /// Rounds dates down to the start of this unit
+    #[serde(skip_serializing_if = "TimeUnit::is_empty", default)]
+    pub round: TimeUnit,
+
+    /// The day weeks start on, from 1 for Monday to 7 for Sunday, when the unit is `week`
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub isoWeekday: NumberString,
+
+    /// The format of date strings in the data, for the date adapter to parse
     #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub unit: String,
+    pub parser: String,
+
+    /// The format of dates in tooltips
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub tooltipFormat: String,
+
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub stepSize: NumberString,
+}
+
+option_enum! {
+    /// A unit of time on a time scale
+    TimeUnit {
+        Millisecond => "millisecond",
+        Second => "second",
+        Minute => "minute",
+        Hour => "hour",
+        Day => "day",
+        Week => "week",
+        Month => "month",
+        Quarter => "quarter",
+        Year => "year",
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -807,6 +1415,12 @@ pub struct DisplayFormats {
 
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub minute: String,
+
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub second: String,
+
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub millisecond: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -825,6 +1439,192 @@ pub struct ScaleTicks {
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub precision: NumberString,
+
+    /// Returns the label of a tick, called with `value`, `index` and `ticks`
+    #[serde(skip_serializing_if = "FnWithArgs::is_empty", default)]
+    pub callback: FnWithArgs,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<bool>,
+
+    /// Skips ticks whose labels would overlap
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autoSkip: Option<bool>,
+
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub autoSkipPadding: NumberString,
+
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub maxRotation: NumberString,
+
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub minRotation: NumberString,
+
+    /// The space between the ticks and the axis
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub padding: NumberString,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font: Option<Font>,
+
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub color: Color,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub major: Option<TicksMajor>,
+
+    /// How numbers are shown when there is no `callback`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<NumberFormatOptions>,
+
+    /// How many ticks are measured to fit the labels, all of them if unset
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub sampleSize: NumberString,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TicksMajor {
+    /// Marks the ticks at the start of a larger unit on time scales, e.g. the first of a month
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+/// The options of `Intl.NumberFormat`, which formats the numbers of ticks and tooltips
+/// in the chart's `locale`
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NumberFormatOptions {
+    #[serde(skip_serializing_if = "NumberStyle::is_empty", default)]
+    pub style: NumberStyle,
+
+    /// An ISO 4217 code, e.g. `EUR`, when `style` is `Currency`
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub currency: String,
+
+    #[serde(skip_serializing_if = "CurrencyDisplay::is_empty", default)]
+    pub currencyDisplay: CurrencyDisplay,
+
+    /// e.g. `kilobyte` or `kilometer-per-hour`, when `style` is `Unit`
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub unit: String,
+
+    #[serde(skip_serializing_if = "UnitDisplay::is_empty", default)]
+    pub unitDisplay: UnitDisplay,
+
+    #[serde(skip_serializing_if = "Notation::is_empty", default)]
+    pub notation: Notation,
+
+    /// How `Compact` notation is written, e.g. `1.2K` or `1.2 thousand`
+    #[serde(skip_serializing_if = "CompactDisplay::is_empty", default)]
+    pub compactDisplay: CompactDisplay,
+
+    #[serde(skip_serializing_if = "SignDisplay::is_empty", default)]
+    pub signDisplay: SignDisplay,
+
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub minimumIntegerDigits: NumberString,
+
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub minimumFractionDigits: NumberString,
+
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub maximumFractionDigits: NumberString,
+
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub minimumSignificantDigits: NumberString,
+
+    #[serde(skip_serializing_if = "NumberString::is_empty", default)]
+    pub maximumSignificantDigits: NumberString,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub useGrouping: Option<bool>,
+}
+
+option_enum! {
+    NumberStyle {
+        Decimal => "decimal",
+        Currency => "currency",
+        Percent => "percent",
+        Unit => "unit",
+    }
+}
+
+option_enum! {
+    CurrencyDisplay {
+        /// e.g. `€`
+        Symbol => "symbol",
+        /// e.g. `$` rather than `US$`
+        NarrowSymbol => "narrowSymbol",
+        /// e.g. `EUR`
+        Code => "code",
+        /// e.g. `euros`
+        Name => "name",
+    }
+}
+
+option_enum! {
+    /// How units are written
+    UnitDisplay {
+        Short => "short",
+        Long => "long",
+        Narrow => "narrow",
+    }
+}
+
+option_enum! {
+    /// How compact numbers are written, which unlike units can't be `narrow`
+    CompactDisplay {
+        Short => "short",
+        Long => "long",
+    }
+}
+
+option_enum! {
+    Notation {
+        Standard => "standard",
+        Scientific => "scientific",
+        Engineering => "engineering",
+        /// e.g. `1.2K`, or `1.2万` in Japanese
+        Compact => "compact",
+    }
+}
+
+option_enum! {
+    SignDisplay {
+        Auto => "auto",
+        Always => "always",
+        ExceptZero => "exceptZero",
+        Negative => "negative",
+        Never => "never",
+    }
+}
+
+/// A BCP 47 language tag, e.g. `de-DE` or `ja-JP`, which decides how numbers and
+/// dates are written. Defaults to the browser's language when empty
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Locale(String);
+impl Locale {
+    pub fn new(tag: &str) -> Self {
+        Self(tag.to_string())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+impl From<&str> for Locale {
+    fn from(tag: &str) -> Self {
+        Self::new(tag)
+    }
+}
+impl From<String> for Locale {
+    fn from(tag: String) -> Self {
+        Self(tag)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -835,6 +1635,9 @@ pub struct Title {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display: Option<bool>,
 
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub color: Color,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub font: Option<Font>,
 }
@@ -844,13 +1647,92 @@ pub struct ChartInteraction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub intersect: Option<bool>,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<InteractionMode>,
 
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub axis: String,
 }
 
+option_enum! {
+    /// How elements are found for hovering, tooltips and hit-testing,
+    /// with `Custom` for modes registered by plugins
+    InteractionMode = Nearest {
+        Nearest => "nearest",
+        Point => "point",
+        Index => "index",
+        Dataset => "dataset",
+        X => "x",
+        Y => "y",
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
+pub struct ActiveElement {
+    pub datasetIndex: usize,
+    pub index: usize,
+    pub value: serde_json::Value,
+}
+
+#[derive(
+    Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct ElementRef {
+    pub datasetIndex: usize,
+    pub index: usize,
+}
+impl From<&ActiveElement> for ElementRef {
+    fn from(value: &ActiveElement) -> Self {
+        Self {
+            datasetIndex: value.datasetIndex,
+            index: value.index,
+        }
+    }
+}
+impl From<ActiveElement> for ElementRef {
+    fn from(value: ActiveElement) -> Self {
+        (&value).into()
+    }
+}
+impl From<(usize, usize)> for ElementRef {
+    fn from((datasetIndex, index): (usize, usize)) -> Self {
+        Self {
+            datasetIndex,
+            index,
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum ImageMime {
+    #[default]
+    #[serde(rename = "image/png")]
+    Png,
+    #[serde(rename = "image/jpeg")]
+    Jpeg,
+    #[serde(rename = "image/webp")]
+    Webp,
+}
+impl ImageMime {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageMime::Png => "image/png",
+            ImageMime::Jpeg => "image/jpeg",
+            ImageMime::Webp => "image/webp",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageMime::Png => "png",
+            ImageMime::Jpeg => "jpg",
+            ImageMime::Webp => "webp",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ChartTooltips {
     #[serde(skip_serializing_if = "String::is_empty", default)]
@@ -883,8 +1765,8 @@ pub struct LegendLabel {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub boxWidth: Option<usize>,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub pointStyle: String,
+    #[serde(skip_serializing_if = "PointStyle::is_empty", default)]
+    pub pointStyle: PointStyle,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub pointStyleWidth: NumberString,
@@ -925,8 +1807,8 @@ pub struct LineElementConfiguration {
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub borderWidth: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub cubicInterpolationMode: String,
+    #[serde(skip_serializing_if = "CubicInterpolationMode::is_empty", default)]
+    pub cubicInterpolationMode: CubicInterpolationMode,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -949,14 +1831,14 @@ pub struct PointElementConfiguration {
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct DataLabels {
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub align: String,
+    #[serde(skip_serializing_if = "Align::is_empty", default)]
+    pub align: Align,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub anchor: String,
+    #[serde(skip_serializing_if = "Anchor::is_empty", default)]
+    pub anchor: Anchor,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub backgroundColor: String,
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub backgroundColor: Color,
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub borderRadius: NumberString,
@@ -964,8 +1846,8 @@ pub struct DataLabels {
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub drawTime: NumberString,
 
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub color: String,
+    #[serde(skip_serializing_if = "Color::is_empty", default)]
+    pub color: Color,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub clip: Option<bool>,
@@ -984,6 +1866,10 @@ pub struct DataLabels {
 
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub z: NumberString,
+
+    /// Returns the text of a label, called with `value` and `context`
+    #[serde(skip_serializing_if = "FnWithArgs::is_empty", default)]
+    pub formatter: FnWithArgs,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -1003,6 +1889,9 @@ pub struct Padding {
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Font {
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub family: String,
+
     #[serde(skip_serializing_if = "NumberString::is_empty", default)]
     pub size: NumberString,
 
@@ -1023,3 +1912,294 @@ pub struct Segment {
     #[serde(skip_serializing_if = "FnWithArgs::is_empty", default)]
     pub borderColor: FnWithArgs,
 }
+
+option_enum! {
+    ScaleType {
+        Linear => "linear",
+        Logarithmic => "logarithmic",
+        Category => "category",
+        Time => "time",
+        TimeSeries => "timeseries",
+        RadialLinear => "radialLinear",
+    }
+}
+
+option_enum! {
+    ScalePosition {
+        Top => "top",
+        Bottom => "bottom",
+        Left => "left",
+        Right => "right",
+        Center => "center",
+    }
+}
+
+option_enum! {
+    /// Whether a scale's range fits the data or the ticks
+    Bounds {
+        Data => "data",
+        Ticks => "ticks",
+    }
+}
+
+option_enum! {
+    PointStyle {
+        Circle => "circle",
+        Cross => "cross",
+        CrossRot => "crossRot",
+        Dash => "dash",
+        Line => "line",
+        Rect => "rect",
+        RectRounded => "rectRounded",
+        RectRot => "rectRot",
+        Star => "star",
+        Triangle => "triangle",
+        /// Draws no point
+        Disabled => false,
+    }
+}
+
+option_enum! {
+    /// The edge of a bar that has no border
+    BorderSkipped {
+        Start => "start",
+        End => "end",
+        Middle => "middle",
+        Bottom => "bottom",
+        Left => "left",
+        Top => "top",
+        Right => "right",
+        /// Draws every border
+        Disabled => false,
+    }
+}
+
+option_enum! {
+    IndexAxis {
+        X => "x",
+        Y => "y",
+    }
+}
+
+option_enum! {
+    CubicInterpolationMode {
+        Default => "default",
+        Monotone => "monotone",
+    }
+}
+
+option_enum! {
+    /// Where a data label sits relative to its anchor
+    Align {
+        Center => "center",
+        Start => "start",
+        End => "end",
+        Right => "right",
+        Bottom => "bottom",
+        Left => "left",
+        Top => "top";
+        /// An angle in degrees, clockwise from the right
+        Angle(NumberString),
+    }
+}
+
+option_enum! {
+    /// The point of an element a data label is placed at
+    Anchor {
+        Center => "center",
+        Start => "start",
+        End => "end",
+    }
+}
+
+/// What a line dataset is filled to
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FillTarget {
+    /// `true` fills to the origin, `false` doesn't fill
+    Bool(bool),
+    Origin,
+    Start,
+    End,
+    Stack,
+    Shape,
+    /// The dataset with this index
+    Dataset(usize),
+    /// The dataset this many places before or after, e.g. `-1` for the previous one
+    Relative(isize),
+    /// A horizontal or vertical line at this value
+    Value(NumberString),
+    Custom(String),
+}
+impl FillTarget {
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Custom(s) if s.is_empty())
+    }
+}
+impl Default for FillTarget {
+    fn default() -> Self {
+        Self::Custom(String::new())
+    }
+}
+impl From<bool> for FillTarget {
+    fn from(b: bool) -> Self {
+        Self::Bool(b)
+    }
+}
+impl From<usize> for FillTarget {
+    fn from(index: usize) -> Self {
+        Self::Dataset(index)
+    }
+}
+impl From<&str> for FillTarget {
+    fn from(s: &str) -> Self {
+        match s {
+            "origin" => Self::Origin,
+            "start" => Self::Start,
+            "end" => Self::End,
+            "stack" => Self::Stack,
+            "shape" => Self::Shape,
+            "true" | "false" => Self::Bool(s == "true"),
+            _ => match (
+                s.parse::<usize>(),
+                s.strip_prefix('+').unwrap_or(s).parse::<isize>(),
+            ) {
+                (Ok(index), _) if !s.starts_with('+') => Self::Dataset(index),
+                (_, Ok(offset)) => Self::Relative(offset),
+                _ => Self::Custom(s.into()),
+            },
+        }
+    }
+}
+impl From<String> for FillTarget {
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
+impl Serialize for FillTarget {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::Bool(b) => serializer.serialize_bool(*b),
+            Self::Origin => serializer.serialize_str("origin"),
+            Self::Start => serializer.serialize_str("start"),
+            Self::End => serializer.serialize_str("end"),
+            Self::Stack => serializer.serialize_str("stack"),
+            Self::Shape => serializer.serialize_str("shape"),
+            Self::Dataset(index) => serializer.serialize_u64(*index as u64),
+            Self::Relative(offset) => serializer.serialize_str(&format!("{offset:+}")),
+            Self::Value(value) => serde_json::json!({ "value": value }).serialize(serializer),
+            Self::Custom(s) => serializer.serialize_str(s),
+        }
+    }
+}
+impl<'de> Deserialize<'de> for FillTarget {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::Bool(b) => Self::Bool(b),
+            serde_json::Value::Number(n) => n.to_string().as_str().into(),
+            serde_json::Value::String(s) => s.into(),
+            serde_json::Value::Object(o) if o.contains_key("value") => match &o["value"] {
+                serde_json::Value::String(s) => Self::Value(s.as_str().into()),
+                value => Self::Value(value.to_string().into()),
+            },
+            value => {
+                return Err(serde::de::Error::custom(format!(
+                    "expected a fill target, found `{value}`"
+                )))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn serializable_data_uses_the_default_conversion() {
+        #[derive(Serialize)]
+        struct Temperatures(Vec<f64>);
+        impl DatasetDataExt for Temperatures {}
+
+        assert_eq!(
+            Temperatures(vec![20.5, 21.0]).to_dataset_data(),
+            DatasetData(json!([20.5, 21.0]))
+        );
+        assert_eq!(
+            Rows(vec![json!({ "t": 1 })]).to_dataset_data(),
+            DatasetData(json!([{ "t": 1 }]))
+        );
+    }
+
+    #[test]
+    fn option_enums_read_back_what_they_write() {
+        for style in [PointStyle::Circle, PointStyle::Disabled, "heart".into()] {
+            let value = serde_json::to_value(&style).unwrap();
+            assert_eq!(serde_json::from_value::<PointStyle>(value).unwrap(), style);
+        }
+        assert_eq!(
+            serde_json::to_value(PointStyle::Disabled).unwrap(),
+            json!(false)
+        );
+        assert_eq!(PointStyle::from("rectRot"), PointStyle::RectRot);
+    }
+
+    #[test]
+    fn option_enums_reject_other_values() {
+        assert!(serde_json::from_value::<PointStyle>(json!(true)).is_err());
+        assert!(serde_json::from_value::<TimeUnit>(json!(5)).is_err());
+    }
+
+    #[test]
+    fn interaction_mode_defaults_to_nearest() {
+        assert_eq!(InteractionMode::default(), InteractionMode::Nearest);
+        assert_eq!(
+            serde_json::to_value(ChartInteraction::default()).unwrap(),
+            json!({})
+        );
+    }
+
+    #[test]
+    fn plugin_interaction_modes_are_kept() {
+        let interaction: ChartInteraction = serde_json::from_str(r#"{"mode":"myMode"}"#).unwrap();
+        assert_eq!(
+            interaction.mode,
+            Some(InteractionMode::Custom("myMode".into()))
+        );
+        assert_eq!(InteractionMode::from("index"), InteractionMode::Index);
+    }
+
+    #[test]
+    fn data_label_alignments_can_be_angles() {
+        let labels: DataLabels = serde_json::from_value(json!({"align": 45})).unwrap();
+        assert_eq!(labels.align, Align::Angle(45.into()));
+        assert_eq!(serde_json::to_value(&labels).unwrap(), json!({"align": 45}));
+        assert!(serde_json::from_value::<Align>(json!(true)).is_err());
+    }
+
+    #[test]
+    fn fill_targets_read_back_what_they_write() {
+        for fill in [
+            FillTarget::Bool(false),
+            FillTarget::Origin,
+            FillTarget::Dataset(2),
+            FillTarget::Relative(-1),
+            FillTarget::Value(25.into()),
+        ] {
+            let value = serde_json::to_value(&fill).unwrap();
+            assert_eq!(serde_json::from_value::<FillTarget>(value).unwrap(), fill);
+        }
+        assert_eq!(
+            serde_json::from_value::<FillTarget>(json!({ "value": "25" })).unwrap(),
+            FillTarget::Value(25.into())
+        );
+        assert!(serde_json::from_value::<FillTarget>(json!([1])).is_err());
+    }
+}
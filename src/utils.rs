@@ -1,8 +1,16 @@
-use js_sys::{Array, Function, Reflect};
+use js_sys::{Array, Function, Object, Reflect};
 use serde::{Deserialize, Serialize};
-use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
 
-use crate::{render_chart, update_chart};
+use crate::{
+    download_image, elements_at, get_active_elements, get_data_visibility, hide,
+    is_dataset_visible, needs_date_adapter, paint, render_chart, set_active_elements,
+    set_dataset_visibility, set_tooltip_active_elements, show, to_base64_image, to_blob,
+    toggle_data_visibility, try_render_chart, update_chart,
+    validate::{validate, Diagnostic},
+    ActiveElement, ChartError, ElementRef, ImageMime, InteractionMode,
+};
 
 #[wasm_bindgen]
 pub struct Chart(pub(crate) JsValue, pub(crate) String);
@@ -31,55 +39,319 @@ impl Chart {
         v.is_object().then_some(Self(v, id))
     }
     pub fn render(self) {
+        self.register_date_adapter();
         self.rationalise_js();
         render_chart(self.0, &self.1, false);
     }
     pub fn render_mutate(self) {
+        self.register_date_adapter();
         self.rationalise_js();
         render_chart(self.0, &self.1, true);
     }
+    /// Renders the chart like [`Chart::render`], but first checks that it can be rendered,
+    /// e.g. that a date adapter is registered if it has a time scale, and returns
+    /// any error Chart.js throws
+    pub fn try_render(self) -> Result<(), ChartError> {
+        self.check_date_adapter()?;
+        self.rationalise_js();
+        try_render_chart(self.0, &self.1, false).map_err(js_error)
+    }
+    /// Renders the chart like [`Chart::render_mutate`], with the checks of [`Chart::try_render`]
+    pub fn try_render_mutate(self) -> Result<(), ChartError> {
+        self.check_date_adapter()?;
+        self.rationalise_js();
+        try_render_chart(self.0, &self.1, true).map_err(js_error)
+    }
     pub fn update(self, animate: bool) -> bool {
+        self.register_date_adapter();
+        self.rationalise_js();
         update_chart(self.0, &self.1, animate)
     }
+    /// Checks the chart for problems Chart.js doesn't report, see [`validate`](crate::validate::validate).
+    /// Returns an error if the chart can't be read back, e.g. once it holds JS functions
+    pub fn validate(&self) -> Result<Vec<Diagnostic>, ChartError> {
+        let config = serde_wasm_bindgen::from_value::<serde_json::Value>(self.0.clone())?;
+        Ok(validate(&config))
+    }
+    pub fn handle(&self) -> ChartHandle {
+        ChartHandle::new(&self.1)
+    }
+
+    /// Errors if the chart has a time scale but no date adapter is registered,
+    /// or registers the built-in one with the `date-adapter` feature
+    fn check_date_adapter(&self) -> Result<(), ChartError> {
+        self.register_date_adapter();
+        if cfg!(not(feature = "date-adapter")) && needs_date_adapter(&self.0) {
+            return Err(ChartError::MissingDateAdapter);
+        }
+        Ok(())
+    }
 
-    /// Converts the string-serialized segment functions to a JavaScript function
+    /// With the `date-adapter` feature, registers the built-in date adapter
+    /// if the chart has a time scale but no adapter is registered
+    fn register_date_adapter(&self) {
+        #[cfg(feature = "date-adapter")]
+        if needs_date_adapter(&self.0) {
+            crate::date_adapter::register_date_adapter();
+        }
+    }
+
+    /// Converts the string-serialized functions ([`FnWithArgs`]) in the options and datasets
+    /// to JavaScript functions, and gradient and pattern specs to scriptable options that create them,
     /// then updates the chart options in the Js representation opf the chart
     pub fn rationalise_js(&self) {
+        if let Some(options) = get_path(&self.0, "options") {
+            rationalise_fns(options);
+        }
+
         Array::from(&get_path(&self.0, "data.datasets").unwrap())
             .iter()
             .for_each(|dataset| {
-                let segment = Reflect::get(&dataset, &"segment".into());
-                if segment.is_ok() {
-                    let segment = segment.unwrap();
-
-                    let dash = Reflect::get(&segment, &"borderDash".into());
-                    if let Ok(dash) = dash {
-                        Reflect::set(
-                            &segment,
-                            &"borderDash".into(),
-                            &serde_wasm_bindgen::from_value::<FnWithArgs>(dash)
-                                .unwrap()
-                                .build(),
-                        )
-                        .unwrap();
-                    }
+                // the data can be large, and never holds functions
+                Object::keys(dataset.unchecked_ref::<Object>())
+                    .iter()
+                    .filter(|key| key != "data")
+                    .for_each(|key| {
+                        let value = Reflect::get(&dataset, &key).unwrap();
+                        Reflect::set(&dataset, &key, &rationalise_fns(value)).unwrap();
+                    });
 
-                    let color = Reflect::get(&segment, &"borderColor".into());
-                    if let Ok(color) = color {
-                        Reflect::set(
-                            &segment,
-                            &"borderColor".into(),
-                            &serde_wasm_bindgen::from_value::<FnWithArgs>(color)
-                                .unwrap()
-                                .build(),
-                        )
-                        .unwrap();
+                PAINT_KEYS.iter().for_each(|key| {
+                    if let Ok(value) = Reflect::get(&dataset, &(*key).into()) {
+                        Reflect::set(&dataset, &(*key).into(), &rationalise_paint(value)).unwrap();
                     }
-                }
+                });
             });
     }
 }
 
+/// A reference to a chart that has already been rendered,
+/// looked up by the id of its canvas each time it is used
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChartHandle(String);
+
+impl ChartHandle {
+    pub fn new(id: &str) -> Self {
+        Self(id.to_string())
+    }
+
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the elements under the pixel `(x, y)` (relative to the canvas),
+    /// as found by `Chart.getElementsAtEventForMode`
+    pub fn elements_at(
+        &self,
+        x: f64,
+        y: f64,
+        mode: impl Into<InteractionMode>,
+        intersect: bool,
+    ) -> Result<Vec<ActiveElement>, ChartError> {
+        let mode = serde_wasm_bindgen::to_value(&mode.into())?;
+        let elements = elements_at(&self.0, x, y, mode, intersect).map_err(|e| self.error(e))?;
+
+        Ok(serde_wasm_bindgen::from_value(elements)?)
+    }
+
+    /// Shows or hides a whole dataset, then updates the chart
+    pub fn set_dataset_visibility(
+        &self,
+        dataset_index: usize,
+        visible: bool,
+    ) -> Result<(), ChartError> {
+        set_dataset_visibility(&self.0, dataset_index, visible).map_err(|e| self.error(e))
+    }
+
+    /// Hides a dataset, or a single point of it if `data_index` is given, with an animation
+    pub fn hide(&self, dataset_index: usize, data_index: Option<usize>) -> Result<(), ChartError> {
+        hide(&self.0, dataset_index, data_index).map_err(|e| self.error(e))
+    }
+
+    /// Shows a dataset, or a single point of it if `data_index` is given, with an animation
+    pub fn show(&self, dataset_index: usize, data_index: Option<usize>) -> Result<(), ChartError> {
+        show(&self.0, dataset_index, data_index).map_err(|e| self.error(e))
+    }
+
+    /// Toggles the visibility of the item at `index` across all datasets,
+    /// e.g. a slice of a pie or doughnut chart, then updates the chart
+    pub fn toggle_data_visibility(&self, index: usize) -> Result<(), ChartError> {
+        toggle_data_visibility(&self.0, index).map_err(|e| self.error(e))
+    }
+
+    pub fn is_dataset_visible(&self, dataset_index: usize) -> Result<bool, ChartError> {
+        is_dataset_visible(&self.0, dataset_index).map_err(|e| self.error(e))
+    }
+
+    pub fn get_data_visibility(&self, index: usize) -> Result<bool, ChartError> {
+        get_data_visibility(&self.0, index).map_err(|e| self.error(e))
+    }
+
+    /// Sets the hovered elements of the chart, pass an empty slice to clear them
+    pub fn set_active_elements(&self, elements: &[ElementRef]) -> Result<(), ChartError> {
+        let elements = serde_wasm_bindgen::to_value(elements)?;
+        set_active_elements(&self.0, elements).map_err(|e| self.error(e))
+    }
+
+    pub fn get_active_elements(&self) -> Result<Vec<ActiveElement>, ChartError> {
+        let elements = get_active_elements(&self.0).map_err(|e| self.error(e))?;
+        Ok(serde_wasm_bindgen::from_value(elements)?)
+    }
+
+    /// Shows the tooltip for the given elements, pass an empty slice to hide it.
+    ///
+    /// `position` is the pixel the tooltip is placed relative to,
+    /// defaulting to the position of the first element
+    pub fn set_tooltip_active_elements(
+        &self,
+        elements: &[ElementRef],
+        position: Option<(f64, f64)>,
+    ) -> Result<(), ChartError> {
+        let elements = serde_wasm_bindgen::to_value(elements)?;
+        let (x, y) = position.unzip();
+        set_tooltip_active_elements(&self.0, elements, x, y).map_err(|e| self.error(e))
+    }
+
+    /// Renders the chart to a data URL, e.g. `data:image/png;base64,...`.
+    ///
+    /// `quality` is between `0.0` and `1.0` and only applies to lossy formats.
+    /// Chart.js canvases are transparent, so pass a `background` such as `"white"` to fill it in
+    pub fn to_base64_image(
+        &self,
+        mime: ImageMime,
+        quality: Option<f64>,
+        background: Option<&str>,
+    ) -> Result<String, ChartError> {
+        to_base64_image(
+            &self.0,
+            mime.as_str(),
+            quality,
+            background.map(str::to_string),
+        )
+        .map_err(|e| self.error(e))
+    }
+
+    /// Renders the chart to a `Blob`, see [`ChartHandle::to_base64_image`] for the arguments
+    pub async fn to_blob(
+        &self,
+        mime: ImageMime,
+        quality: Option<f64>,
+        background: Option<&str>,
+    ) -> Result<web_sys::Blob, ChartError> {
+        let promise = to_blob(
+            &self.0,
+            mime.as_str(),
+            quality,
+            background.map(str::to_string),
+        )
+        .map_err(|e| self.error(e))?;
+
+        JsFuture::from(promise)
+            .await
+            .map(JsCast::unchecked_into)
+            .map_err(|e| self.error(e))
+    }
+
+    /// Triggers a browser download of the chart as an image named `file_name`,
+    /// see [`ChartHandle::to_base64_image`] for the other arguments
+    pub fn download(
+        &self,
+        file_name: &str,
+        mime: ImageMime,
+        quality: Option<f64>,
+        background: Option<&str>,
+    ) -> Result<(), ChartError> {
+        download_image(
+            &self.0,
+            file_name,
+            mime.as_str(),
+            quality,
+            background.map(str::to_string),
+        )
+        .map_err(|e| self.error(e))
+    }
+
+    pub(crate) fn error(&self, e: JsValue) -> ChartError {
+        match e.as_string() {
+            Some(e) if e == "ChartNotFound" => ChartError::ChartNotFound(self.0.clone()),
+            _ => js_error(e),
+        }
+    }
+}
+
+/// Converts a value thrown by Chart.js into a [`ChartError`]
+pub(crate) fn js_error(e: JsValue) -> ChartError {
+    match e.as_string() {
+        Some(e) => ChartError::JsError(e),
+        None => match e.dyn_ref::<js_sys::Error>() {
+            Some(e) => ChartError::JsError(e.message().into()),
+            None => ChartError::JsError(format!("{e:?}")),
+        },
+    }
+}
+
+/// Replaces the objects with only `args` and `body`, the serialized [`FnWithArgs`],
+/// with the functions they describe, anywhere within `value`
+fn rationalise_fns(value: JsValue) -> JsValue {
+    if Array::is_array(&value) {
+        let array = Array::from(&value);
+        array
+            .iter()
+            .enumerate()
+            .for_each(|(i, item)| array.set(i as u32, rationalise_fns(item)));
+        return value;
+    }
+    if !value.is_object() {
+        return value;
+    }
+
+    let keys = Object::keys(value.unchecked_ref::<Object>());
+    let is_fn =
+        keys.length() == 2 && keys.includes(&"args".into(), 0) && keys.includes(&"body".into(), 0);
+    if is_fn {
+        if let Ok(f) = serde_wasm_bindgen::from_value::<FnWithArgs>(value.clone()) {
+            return f.build().into();
+        }
+    }
+
+    keys.iter().for_each(|key| {
+        let item = Reflect::get(&value, &key).unwrap();
+        Reflect::set(&value, &key, &rationalise_fns(item)).unwrap();
+    });
+    value
+}
+
+/// The dataset options that can hold a [`Paint`](crate::Paint)
+pub(crate) const PAINT_KEYS: [&str; 7] = [
+    "backgroundColor",
+    "borderColor",
+    "hoverBackgroundColor",
+    "hoverBorderColor",
+    "pointBackgroundColor",
+    "pointBorderColor",
+    "pointHoverBackgroundColor",
+];
+
+fn rationalise_paint(value: JsValue) -> JsValue {
+    if Array::is_array(&value) {
+        return Array::from(&value)
+            .iter()
+            .map(rationalise_paint)
+            .collect::<Array>()
+            .into();
+    }
+
+    let is_paint = value.is_object()
+        && ["gradient", "pattern"]
+            .iter()
+            .any(|key| Reflect::has(&value, &(*key).into()).unwrap_or(false));
+    if is_paint {
+        paint(value)
+    } else {
+        value
+    }
+}
+
 #[derive(Default, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct FnWithArgs {
     pub args: Vec<String>,
@@ -0,0 +1,591 @@
+use std::fmt::Display;
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::{utils::PAINT_KEYS, Color};
+
+/// The chart and dataset `type`s that come with Chart.js
+const CHART_TYPES: [&str; 8] = [
+    "bar",
+    "bubble",
+    "doughnut",
+    "line",
+    "pie",
+    "polarArea",
+    "radar",
+    "scatter",
+];
+/// The scale `type`s that come with Chart.js
+const SCALE_TYPES: [&str; 6] = [
+    "category",
+    "linear",
+    "logarithmic",
+    "radialLinear",
+    "time",
+    "timeseries",
+];
+/// The chart types drawn without `x` and `y` scales
+const RADIAL_TYPES: [&str; 4] = ["doughnut", "pie", "polarArea", "radar"];
+
+/// Something wrong with a chart config, found by [`validate`]
+#[derive(Debug, Clone, Error, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Problem {
+    #[error("the dataset is drawn on scale `{0}`, which isn't configured")]
+    MissingAxis(String),
+
+    #[error("the dataset has {data} values, but the chart has {labels} labels")]
+    LengthMismatch { labels: usize, data: usize },
+
+    #[error("`{0}` is on a time scale, but isn't a timestamp or an ISO 8601 date")]
+    NotADate(String),
+
+    #[error("`[min, max]` points can only be drawn by bar charts")]
+    MinMaxPoint,
+
+    #[error("the chart has no datasets")]
+    NoDatasets,
+
+    #[error("the dataset has no data")]
+    EmptyDataset,
+
+    #[error("`{0}` isn't a type that comes with Chart.js")]
+    UnknownType(String),
+
+    #[error("`{0}` is not a valid CSS color")]
+    InvalidColor(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Probably fine, e.g. a type registered by a plugin, or data that is loaded later
+    Warning,
+    /// Drawn wrongly, or not at all
+    Error,
+}
+
+impl Problem {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::NoDatasets | Self::EmptyDataset | Self::UnknownType(_) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// A [`Problem`] and where it is in the config, as a path like `data.datasets[0].yAxisID`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Diagnostic {
+    pub path: String,
+    pub problem: Problem,
+}
+
+impl Diagnostic {
+    pub fn severity(&self) -> Severity {
+        self.problem.severity()
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity() == Severity::Error
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.problem)
+    }
+}
+
+/// Checks a chart config, as serialized by [`ChartExt::validate`](crate::ChartExt::validate)
+/// or [`Chart::validate`](crate::utils::Chart::validate), for problems that Chart.js
+/// doesn't report, and returns them in the order they appear
+pub fn validate(config: &Value) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut report = |path: String, problem: Problem| {
+        diagnostics.push(Diagnostic { path, problem });
+    };
+
+    let chart_type = config["type"].as_str().unwrap_or_default();
+    if !CHART_TYPES.contains(&chart_type) {
+        report("type".to_string(), Problem::UnknownType(chart_type.into()));
+    }
+
+    let empty = Map::new();
+    let scales = config["options"]["scales"].as_object().unwrap_or(&empty);
+    for (id, scale) in scales {
+        match scale["type"].as_str() {
+            Some(t) if !t.is_empty() && !SCALE_TYPES.contains(&t) => report(
+                format!("options.scales.{id}.type"),
+                Problem::UnknownType(t.into()),
+            ),
+            _ => {}
+        }
+    }
+    // time scales with a `parser` can read other values as dates
+    let is_time_scale = |id: &str| {
+        scales.get(id).is_some_and(|scale| {
+            matches!(scale["type"].as_str(), Some("time" | "timeseries"))
+                && scale["time"]["parser"].is_null()
+        })
+    };
+
+    let labels = config["data"]["labels"].as_array();
+    let index_axis = config["options"]["indexAxis"].as_str();
+    let datasets = config["data"]["datasets"].as_array().map(Vec::as_slice);
+    if datasets.unwrap_or_default().is_empty() {
+        report("data.datasets".to_string(), Problem::NoDatasets);
+    }
+
+    let mut labels_checked = false;
+    for (i, dataset) in datasets.unwrap_or_default().iter().enumerate() {
+        let path = format!("data.datasets[{i}]");
+
+        let dataset_type = match dataset["type"].as_str() {
+            Some(t) if !t.is_empty() => {
+                if !CHART_TYPES.contains(&t) {
+                    report(format!("{path}.type"), Problem::UnknownType(t.into()));
+                }
+                t
+            }
+            _ => chart_type,
+        };
+        let cartesian = !RADIAL_TYPES.contains(&dataset_type);
+
+        let mut axes = Vec::new();
+        for (key, default) in [("xAxisID", "x"), ("yAxisID", "y")] {
+            let id = dataset[key].as_str().filter(|id| !id.is_empty());
+            if let Some(id) = id.filter(|id| cartesian && *id != default) {
+                if !scales.contains_key(id) {
+                    report(format!("{path}.{key}"), Problem::MissingAxis(id.into()));
+                }
+            }
+            axes.push(id.unwrap_or(default));
+        }
+
+        let data = dataset["data"].as_array().map(Vec::as_slice);
+        let data = data.unwrap_or_default();
+        if data.is_empty() {
+            report(format!("{path}.data"), Problem::EmptyDataset);
+        }
+
+        if dataset_type != "bar" {
+            if let Some(j) = data.iter().position(Value::is_array) {
+                report(format!("{path}.data[{j}]"), Problem::MinMaxPoint);
+            }
+        }
+
+        // values, or `[min, max]` points, are drawn at the label with the same index
+        let by_label = data.iter().all(|v| !v.is_object());
+        if let Some(labels) = labels.filter(|_| by_label && !data.is_empty()) {
+            if labels.len() != data.len() {
+                report(
+                    format!("{path}.data"),
+                    Problem::LengthMismatch {
+                        labels: labels.len(),
+                        data: data.len(),
+                    },
+                );
+            }
+        }
+
+        if cartesian {
+            // the labels are on the y axis of horizontal charts
+            let label_axis = match dataset["indexAxis"].as_str().or(index_axis) {
+                Some("y") => "y",
+                _ => "x",
+            };
+            for (id, field) in axes.iter().zip(["x", "y"]) {
+                if !is_time_scale(id) {
+                    continue;
+                }
+                if by_label && field == label_axis {
+                    // the labels are the dates, and only need checking once
+                    let bad = labels
+                        .filter(|_| !labels_checked)
+                        .and_then(|labels| labels.iter().enumerate().find(|(_, v)| !is_date(v)));
+                    if let Some((j, value)) = bad {
+                        report(format!("data.labels[{j}]"), not_a_date(value));
+                    }
+                    labels_checked = true;
+                } else if !by_label {
+                    let bad = data.iter().enumerate().find(|(_, v)| !is_date(&v[field]));
+                    if let Some((j, value)) = bad {
+                        report(
+                            format!("{path}.data[{j}].{field}"),
+                            not_a_date(&value[field]),
+                        );
+                    }
+                }
+            }
+        }
+
+        for key in PAINT_KEYS {
+            let colors = match &dataset[key] {
+                Value::Array(colors) => colors
+                    .iter()
+                    .enumerate()
+                    .map(|(k, c)| (Some(k), c))
+                    .collect(),
+                color => vec![(None, color)],
+            };
+            for (k, color) in colors {
+                let Some(color) = color.as_str().filter(|c| !c.is_empty()) else {
+                    // gradients and patterns
+                    continue;
+                };
+                if !Color::from(color).is_valid() {
+                    let path = match k {
+                        Some(k) => format!("{path}.{key}[{k}]"),
+                        None => format!("{path}.{key}"),
+                    };
+                    report(path, Problem::InvalidColor(color.into()));
+                }
+            }
+        }
+    }
+
+    let mut colors = Vec::new();
+    find_colors("options".into(), &config["options"], &mut colors);
+    for (path, color) in colors {
+        if !color.is_empty() && !Color::from(color).is_valid() {
+            report(path, Problem::InvalidColor(color.into()));
+        }
+    }
+
+    diagnostics
+}
+
+/// The strings in fields named `color` or ending in `Color`, e.g. `bodyColor`, and their paths
+fn find_colors<'a>(path: String, value: &'a Value, found: &mut Vec<(String, &'a str)>) {
+    let fields = match value {
+        Value::Object(fields) => fields,
+        Value::Array(values) => {
+            for (k, value) in values.iter().enumerate() {
+                find_colors(format!("{path}[{k}]"), value, found);
+            }
+            return;
+        }
+        _ => return,
+    };
+    for (key, value) in fields {
+        let path = format!("{path}.{key}");
+        if key != "color" && !key.ends_with("Color") {
+            find_colors(path, value, found);
+            continue;
+        }
+        // functions and gradients aren't strings
+        match value {
+            Value::String(color) => found.push((path, color)),
+            Value::Array(colors) => {
+                for (k, color) in colors.iter().enumerate() {
+                    if let Some(color) = color.as_str() {
+                        found.push((format!("{path}[{k}]"), color));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn not_a_date(value: &Value) -> Problem {
+    match value {
+        Value::String(s) => Problem::NotADate(s.clone()),
+        value => Problem::NotADate(value.to_string()),
+    }
+}
+
+/// A timestamp, a gap, or a string starting with an ISO 8601 date like `2024`, `2024-01` or `2024-01-31`
+fn is_date(value: &Value) -> bool {
+    let date = match value {
+        Value::Null | Value::Number(_) => return true,
+        Value::String(s) => s.split(['T', ' ']).next().unwrap_or_default(),
+        _ => return false,
+    };
+    let parts = date.split('-').collect::<Vec<_>>();
+    let digits = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+
+    (1..=3).contains(&parts.len())
+        && digits(parts[0], 4)
+        && parts[1..].iter().all(|part| digits(part, 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn problems(config: Value) -> Vec<(String, Problem)> {
+        validate(&config)
+            .into_iter()
+            .map(|d| (d.path, d.problem))
+            .collect()
+    }
+
+    fn found(path: &str, problem: Problem) -> Vec<(String, Problem)> {
+        vec![(path.to_string(), problem)]
+    }
+
+    #[test]
+    fn valid_chart() {
+        let config = json!({
+            "type": "bar",
+            "data": {
+                "labels": ["a", "b"],
+                "datasets": [{ "data": [1, [2, 3]], "backgroundColor": ["red", "#00ff0080"] }],
+            },
+        });
+        assert_eq!(problems(config), vec![]);
+    }
+
+    #[test]
+    fn missing_axis() {
+        let config = json!({
+            "type": "line",
+            "data": { "datasets": [
+                { "data": [{ "x": 1, "y": 2 }], "xAxisID": "x", "yAxisID": "y1" },
+                { "data": [{ "x": 1, "y": 2 }], "yAxisID": "y2" },
+            ] },
+            "options": { "scales": { "y2": { "type": "linear" } } },
+        });
+        assert_eq!(
+            problems(config),
+            found(
+                "data.datasets[0].yAxisID",
+                Problem::MissingAxis("y1".into())
+            )
+        );
+    }
+
+    #[test]
+    fn missing_axis_ignored_by_radial_charts() {
+        let config = json!({
+            "type": "pie",
+            "data": { "datasets": [{ "data": [1], "xAxisID": "x1" }] },
+        });
+        assert_eq!(problems(config), vec![]);
+    }
+
+    #[test]
+    fn length_mismatch() {
+        let config = json!({
+            "type": "doughnut",
+            "data": { "labels": ["a", "b"], "datasets": [{ "data": [1, 2, 3] }] },
+        });
+        assert_eq!(
+            problems(config),
+            found(
+                "data.datasets[0].data",
+                Problem::LengthMismatch { labels: 2, data: 3 }
+            )
+        );
+
+        // points have their own x values
+        let config = json!({
+            "type": "line",
+            "data": { "labels": ["a"], "datasets": [{ "data": [{ "x": 1, "y": 2 }, { "x": 2, "y": 3 }] }] },
+        });
+        assert_eq!(problems(config), vec![]);
+    }
+
+    #[test]
+    fn labels_not_dates() {
+        let config = json!({
+            "type": "line",
+            "data": {
+                "labels": ["2024-01-01", 1704153600000_i64, "Jan 3"],
+                "datasets": [{ "data": [1, 2, 3] }, { "data": [4, 5, 6] }],
+            },
+            "options": { "scales": { "x": { "type": "time" } } },
+        });
+        assert_eq!(
+            problems(config),
+            found("data.labels[2]", Problem::NotADate("Jan 3".into()))
+        );
+    }
+
+    #[test]
+    fn labels_not_dates_on_horizontal_charts() {
+        let config = json!({
+            "type": "bar",
+            "data": { "labels": ["2024-01", "soon"], "datasets": [{ "data": [1, 2] }] },
+            "options": { "indexAxis": "y", "scales": { "y": { "type": "timeseries" } } },
+        });
+        assert_eq!(
+            problems(config),
+            found("data.labels[1]", Problem::NotADate("soon".into()))
+        );
+
+        // the labels are on the y axis, so a time x axis doesn't read them
+        let config = json!({
+            "type": "bar",
+            "data": { "labels": ["a"], "datasets": [{ "data": [1] }] },
+            "options": { "indexAxis": "y", "scales": { "x": { "type": "time" } } },
+        });
+        assert_eq!(problems(config), vec![]);
+    }
+
+    #[test]
+    fn points_not_dates() {
+        let config = json!({
+            "type": "scatter",
+            "data": { "datasets": [{ "data": [
+                { "x": "2024-01-31T09:30:00.000Z", "y": 1 },
+                { "x": true, "y": 2 },
+            ] }] },
+            "options": { "scales": { "x": { "type": "time" } } },
+        });
+        assert_eq!(
+            problems(config),
+            found(
+                "data.datasets[0].data[1].x",
+                Problem::NotADate("true".into())
+            )
+        );
+
+        // a parser reads other formats
+        let config = json!({
+            "type": "scatter",
+            "data": { "datasets": [{ "data": [{ "x": "31/01/2024", "y": 1 }] }] },
+            "options": { "scales": { "x": { "type": "time", "time": { "parser": "dd/MM/yyyy" } } } },
+        });
+        assert_eq!(problems(config), vec![]);
+    }
+
+    #[test]
+    fn min_max_point() {
+        let config = json!({
+            "type": "bar",
+            "data": { "labels": ["a", "b"], "datasets": [
+                { "data": [[1, 2], [3, 4]] },
+                { "type": "line", "data": [1, [3, 4]] },
+            ] },
+        });
+        assert_eq!(
+            problems(config),
+            found("data.datasets[1].data[1]", Problem::MinMaxPoint)
+        );
+    }
+
+    #[test]
+    fn no_data() {
+        let config = json!({ "type": "bar", "data": { "datasets": [] } });
+        assert_eq!(
+            problems(config),
+            found("data.datasets", Problem::NoDatasets)
+        );
+
+        let config = json!({ "type": "bar", "data": { "datasets": [{ "data": [1] }, {}] } });
+        let diagnostics = validate(&config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "data.datasets[1].data");
+        assert_eq!(diagnostics[0].problem, Problem::EmptyDataset);
+        assert_eq!(diagnostics[0].severity(), Severity::Warning);
+    }
+
+    #[test]
+    fn unknown_types() {
+        let config = json!({
+            "type": "sankey",
+            "data": { "datasets": [
+                { "type": "line", "data": [1] },
+                { "type": "matrix", "data": [1] },
+            ] },
+            "options": { "scales": {
+                "x": { "type": "category" },
+                "y": { "type": "linaer" },
+            } },
+        });
+        assert_eq!(
+            problems(config),
+            vec![
+                ("type".into(), Problem::UnknownType("sankey".into())),
+                (
+                    "options.scales.y.type".into(),
+                    Problem::UnknownType("linaer".into())
+                ),
+                (
+                    "data.datasets[1].type".into(),
+                    Problem::UnknownType("matrix".into())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_colors() {
+        let config = json!({
+            "type": "bar",
+            "data": { "datasets": [{
+                "data": [1, 2],
+                "borderColor": "bleu",
+                "backgroundColor": ["red", "rgb(0, 0, 0, 0.2)", { "type": "linear" }],
+            }] },
+        });
+        let diagnostics = validate(&config);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (d.path.as_str(), &d.problem))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "data.datasets[0].backgroundColor[1]",
+                    &Problem::InvalidColor("rgb(0, 0, 0, 0.2)".into())
+                ),
+                (
+                    "data.datasets[0].borderColor",
+                    &Problem::InvalidColor("bleu".into())
+                ),
+            ]
+        );
+        assert!(diagnostics.iter().all(Diagnostic::is_error));
+        assert_eq!(
+            diagnostics[1].to_string(),
+            "data.datasets[0].borderColor: `bleu` is not a valid CSS color"
+        );
+    }
+
+    #[test]
+    fn invalid_option_colors() {
+        let config = json!({
+            "type": "bar",
+            "data": { "datasets": [{ "data": [1] }] },
+            "options": {
+                "color": "#666",
+                "scales": { "y": {
+                    "grid": { "color": ["red", "gren"] },
+                    "border": { "color": "rgb(0, 0, 0, 0.2)" },
+                    "ticks": { "callback": { "args": ["value"], "body": "return value" } },
+                } },
+                "plugins": {
+                    "title": { "color": "blu" },
+                    "tooltip": { "bodyColor": "#fffff", "backgroundColor": "" },
+                    "datalabels": { "color": "white" },
+                },
+            },
+        });
+        assert_eq!(
+            problems(config),
+            vec![
+                (
+                    "options.plugins.title.color".into(),
+                    Problem::InvalidColor("blu".into())
+                ),
+                (
+                    "options.plugins.tooltip.bodyColor".into(),
+                    Problem::InvalidColor("#fffff".into())
+                ),
+                (
+                    "options.scales.y.border.color".into(),
+                    Problem::InvalidColor("rgb(0, 0, 0, 0.2)".into())
+                ),
+                (
+                    "options.scales.y.grid.color[1]".into(),
+                    Problem::InvalidColor("gren".into())
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "derive")]
+
+use chart_js_rs::{ChartData, ChartPointsExt, DatasetDataExt, XYPoint};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+#[cfg(feature = "chrono")]
+#[derive(ChartData)]
+struct Reading {
+    #[chart(x)]
+    taken_at: DateTime<Utc>,
+    #[chart(y)]
+    value: f64,
+    #[chart(label)]
+    station: String,
+}
+
+#[cfg(feature = "chrono")]
+#[derive(ChartData)]
+struct Daily(#[chart(x)] NaiveDate, #[chart(y)] u32);
+
+#[derive(ChartData)]
+struct Named {
+    #[chart(x)]
+    name: String,
+    #[chart(y)]
+    value: f64,
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn dates_become_timestamps() {
+    let reading = Reading {
+        taken_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        value: 2.5,
+        station: "north".into(),
+    };
+    let point = XYPoint::from(&reading);
+    assert_eq!(point.x.to_string(), "1704067200000");
+    assert_eq!(point.y.to_string(), "2.5");
+    assert_eq!(point.description.to_string(), "north");
+
+    let daily = XYPoint::from(Daily(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(), 3));
+    assert_eq!(daily.x.to_string(), "1704153600000");
+}
+
+#[test]
+fn other_values_use_display() {
+    let point = XYPoint::from(Named {
+        name: "Jan".into(),
+        value: 1.0,
+    });
+    assert_eq!(point.x.to_string(), "Jan");
+    assert_eq!(point.y.to_string(), "1");
+}
+
+#[test]
+fn lists_become_dataset_data() {
+    let named = vec![Named {
+        name: "Jan".into(),
+        value: 1.0,
+    }];
+    let points = vec![XYPoint {
+        x: "Jan".into(),
+        y: 1.into(),
+        ..Default::default()
+    }];
+    assert_eq!(
+        named.as_slice().to_dataset_data(),
+        points.clone().to_dataset_data()
+    );
+    assert_eq!(named.to_dataset_data(), points.into());
+}